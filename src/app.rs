@@ -1,185 +1,2492 @@
-use crate::counter_strike::{create_server_process, CS2APPID};
-use crate::{counter_strike, steam, utils};
+use crate::counter_strike::{CounterStrike2, CS2APPID};
+use crate::demos::DemoFile;
+use crate::duration::ConvarDuration;
+use crate::game::GameDefinition;
+use crate::installations::{Installation, InstallationDetails};
+use crate::log_receiver::{LogReceiver, LogReceiverSettings};
+use crate::match_control::MatchAction;
+use crate::match_events::{LineSource, MatchEvent, Team};
+use crate::match_export::{ExportedMatch, MatchSummary};
+use crate::players::{PlayerAction, PlayerRoster};
+use crate::readiness::{Readiness, ServerState};
+use crate::round_backups::RoundBackup;
+use crate::scoreboard::Scoreboard;
+use crate::server::ServerProcess;
+use crate::session_log::{LogFile, LogRotation, SessionLog};
+use crate::settings::{LegacyLayout, ServerSettings};
+use crate::stats_db::{LeaderboardEntry, StatsDb};
+use crate::watchdog::{Watchdog, WatchdogSettings};
+use crate::{
+    convars, demos, game, installations, launch, match_events, match_export, round_backups,
+    scanner, session_log, steam, steamcmd, utils, validation,
+};
 use egui::{Color32, CursorIcon, Style, Visuals};
 use linked_hash_map::LinkedHashMap;
 use std::collections::HashMap;
-use std::process::{Child, Command};
+use std::fs;
+use std::io;
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::string::String;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+
+const SERVER_OUTPUT_LINES: usize = 500;
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_BAN_MINUTES: u32 = 30;
+const STEAMCMD_OUTPUT_LINES: usize = 200;
+const STEAMCMD_INSTALLATION_LABEL: &str = &"SteamCMD";
 
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct CS2ServerPrestarterApp {
     app_id: u32,
 
-    // 1/0
-    insecure: bool,
+    settings: ServerSettings,
+
+    // custom install directory, takes precedence over Steam detection
+    game_path_str: String,
+
+    #[serde(skip)]
+    game_path: String,
+
+    #[serde(skip)]
+    error_title: String,
+
+    #[serde(skip)]
+    discovery_problems: Vec<steam::DiscoveryError>,
+
+    #[serde(skip)]
+    ready: bool,
+
+    #[serde(skip)]
+    server_process: Option<ServerProcess>,
+
+    #[serde(skip)]
+    readiness: Readiness,
+
+    #[serde(skip)]
+    server_output: Vec<String>,
+
+    #[serde(skip)]
+    console_window_open: bool,
+
+    #[serde(skip)]
+    scoreboard: Scoreboard,
+
+    #[serde(skip)]
+    scoreboard_window_open: bool,
+
+    #[serde(skip)]
+    recording_demo: Option<String>,
+
+    #[serde(skip)]
+    demos_window_open: bool,
+
+    #[serde(skip)]
+    demo_files: Vec<DemoFile>,
+
+    #[serde(skip)]
+    selected_demo: Option<PathBuf>,
+
+    #[serde(skip)]
+    demo_new_name: String,
+
+    #[serde(skip)]
+    demo_move_dir: String,
+
+    #[serde(skip)]
+    demo_status: String,
+
+    // selected demo is deleted only after second confirmation
+    #[serde(skip)]
+    demo_delete_pending: bool,
+
+    #[serde(skip)]
+    players_window_open: bool,
+
+    #[serde(skip)]
+    roster: PlayerRoster,
+
+    #[serde(skip)]
+    status_requested_at: Option<Instant>,
+
+    // 0 bans permanently
+    #[serde(skip)]
+    ban_minutes: u32,
+
+    #[serde(skip)]
+    players_status: String,
+
+    // checked on launch, plugin actions are disabled without it
+    #[serde(skip)]
+    admin_plugin: bool,
+
+    #[serde(skip)]
+    match_control_window_open: bool,
+
+    // map picked for `changelevel`
+    #[serde(skip)]
+    control_map: String,
+
+    #[serde(skip)]
+    match_control_status: String,
+
+    #[serde(skip)]
+    backups_window_open: bool,
+
+    #[serde(skip)]
+    round_backups: Vec<RoundBackup>,
+
+    #[serde(skip)]
+    backup_status: String,
+
+    #[serde(skip)]
+    matches_window_open: bool,
+
+    #[serde(skip)]
+    past_matches: Vec<ExportedMatch>,
+
+    #[serde(skip)]
+    selected_match: Option<usize>,
+
+    #[serde(skip)]
+    leaderboard_window_open: bool,
+
+    #[serde(skip)]
+    leaderboard: Vec<LeaderboardEntry>,
+
+    // `YYYY-MM-DD`, empty for no limit
+    #[serde(skip)]
+    leaderboard_from: String,
+
+    #[serde(skip)]
+    leaderboard_to: String,
+
+    #[serde(skip)]
+    leaderboard_status: String,
+
+    log_receiver_settings: LogReceiverSettings,
+
+    #[serde(skip)]
+    log_receiver: Option<LogReceiver>,
+
+    #[serde(skip)]
+    log_receiver_status: String,
+
+    #[serde(skip)]
+    log_receiver_window_open: bool,
+
+    // receiver URL in launch arguments, reused by watchdog restarts
+    #[serde(skip)]
+    launch_log_address: Option<String>,
+
+    // receiver URL running server sends logs to
+    #[serde(skip)]
+    registered_log_address: Option<String>,
+
+    #[serde(skip)]
+    session_log: Option<SessionLog>,
+
+    log_rotation: LogRotation,
+
+    #[serde(skip)]
+    logs_window_open: bool,
+
+    #[serde(skip)]
+    log_files: Vec<LogFile>,
+
+    #[serde(skip)]
+    selected_log: Option<PathBuf>,
+
+    #[serde(skip)]
+    log_content: String,
+
+    #[serde(skip)]
+    log_search: String,
+
+    // lines of `log_content` matching `log_filter`, recomputed when search or file changes
+    #[serde(skip)]
+    log_lines: Vec<Range<usize>>,
+
+    #[serde(skip)]
+    log_filter: Option<String>,
+
+    #[serde(skip)]
+    log_export_path: String,
+
+    #[serde(skip)]
+    log_status: String,
+
+    // arguments of last launch, reused by watchdog restarts
+    #[serde(skip)]
+    server_args: Vec<String>,
+
+    #[serde(skip)]
+    server_envs: HashMap<String, String>,
+
+    #[serde(skip)]
+    available_maps: Vec<String>,
+
+    #[serde(skip)]
+    error_popup_open: bool,
+
+    #[serde(skip)]
+    available_steam_apps: LinkedHashMap<u32, steam::SteamApp>,
+
+    #[serde(skip)]
+    manual_game_dir: String,
+
+    #[serde(skip)]
+    install_checks: Vec<game::InstallCheck>,
+
+    #[serde(skip)]
+    scan_events: Option<Receiver<scanner::ScanEvent>>,
+
+    #[serde(skip)]
+    scan_progress: String,
+
+    #[serde(skip)]
+    game_version: Option<game::GameVersion>,
+
+    #[serde(skip)]
+    app_manifest: Option<steam::AppManifest>,
+
+    #[serde(skip)]
+    launch_warnings: Vec<String>,
+
+    // saved output of `cvarlist` or `find` from server console
+    convar_dump_path: String,
+
+    #[serde(skip)]
+    catalog_window_open: bool,
+
+    #[serde(skip)]
+    catalog_entries: Vec<convars::ConVar>,
+
+    // parsed on first comparison
+    #[serde(skip)]
+    bundled_catalog: Option<Vec<convars::ConVar>>,
+
+    #[serde(skip)]
+    catalog_diff: Option<convars::CatalogDiff>,
+
+    #[serde(skip)]
+    catalog_status: String,
+
+    // where parsed dump is saved, existing files are never replaced
+    #[serde(skip)]
+    catalog_save_path: String,
+
+    #[serde(skip)]
+    show_login_token: bool,
+
+    #[serde(skip)]
+    settings_file_window_open: bool,
+
+    settings_file_path: String,
+
+    // login token stays out of exported file unless checked
+    #[serde(skip)]
+    export_login_token: bool,
+
+    #[serde(skip)]
+    settings_file_status: String,
+
+    // overwriting a file and importing both wait for confirmation
+    #[serde(skip)]
+    confirm_settings_export: bool,
+
+    #[serde(skip)]
+    confirm_settings_import: bool,
+
+    steamcmd_path: String,
+
+    // separate dedicated server copy managed by SteamCMD
+    steamcmd_install_dir: String,
+
+    steamcmd_validate: bool,
+
+    #[serde(skip)]
+    steamcmd_window_open: bool,
+
+    #[serde(skip)]
+    steamcmd_events: Option<Receiver<steamcmd::SteamCmdEvent>>,
+
+    #[serde(skip)]
+    steamcmd_progress: Option<steamcmd::Progress>,
+
+    #[serde(skip)]
+    steamcmd_output: Vec<String>,
+
+    #[serde(skip)]
+    steamcmd_status: String,
+
+    installations: Vec<Installation>,
+
+    #[serde(skip)]
+    installations_window_open: bool,
+
+    #[serde(skip)]
+    installation_details: HashMap<String, InstallationDetails>,
+
+    #[serde(skip)]
+    new_installation_label: String,
+
+    #[serde(skip)]
+    new_installation_path: String,
+
+    watchdog_settings: WatchdogSettings,
+
+    #[serde(skip)]
+    watchdog: Watchdog,
+
+    #[serde(skip)]
+    watchdog_window_open: bool,
+}
+
+impl Default for CS2ServerPrestarterApp {
+    fn default() -> Self {
+        Self {
+            game_path: "".to_string(),
+            ready: false,
+            app_id: *CS2APPID,
+            game_path_str: "".to_string(),
+            settings: ServerSettings::default(),
+
+            discovery_problems: Vec::new(),
+            error_title: "".to_string(),
+            available_maps: Vec::new(),
+            server_process: None,
+            readiness: Readiness::default(),
+            server_output: Vec::new(),
+            console_window_open: false,
+            scoreboard: Scoreboard::default(),
+            scoreboard_window_open: false,
+            recording_demo: None,
+            demos_window_open: false,
+            demo_files: Vec::new(),
+            selected_demo: None,
+            demo_new_name: "".to_string(),
+            demo_move_dir: "".to_string(),
+            demo_status: "".to_string(),
+            demo_delete_pending: false,
+            players_window_open: false,
+            roster: PlayerRoster::default(),
+            status_requested_at: None,
+            ban_minutes: DEFAULT_BAN_MINUTES,
+            players_status: "".to_string(),
+            admin_plugin: false,
+            match_control_window_open: false,
+            control_map: "".to_string(),
+            match_control_status: "".to_string(),
+            backups_window_open: false,
+            round_backups: Vec::new(),
+            backup_status: "".to_string(),
+            matches_window_open: false,
+            past_matches: Vec::new(),
+            selected_match: None,
+            leaderboard_window_open: false,
+            leaderboard: Vec::new(),
+            leaderboard_from: "".to_string(),
+            leaderboard_to: "".to_string(),
+            leaderboard_status: "".to_string(),
+            log_receiver_settings: LogReceiverSettings::default(),
+            log_receiver: None,
+            log_receiver_status: "".to_string(),
+            log_receiver_window_open: false,
+            launch_log_address: None,
+            registered_log_address: None,
+            session_log: None,
+            log_rotation: LogRotation::default(),
+            logs_window_open: false,
+            log_files: Vec::new(),
+            selected_log: None,
+            log_content: "".to_string(),
+            log_search: "".to_string(),
+            log_lines: Vec::new(),
+            log_filter: None,
+            log_export_path: "".to_string(),
+            log_status: "".to_string(),
+            server_args: Vec::new(),
+            server_envs: HashMap::new(),
+
+            error_popup_open: true,
+            available_steam_apps: LinkedHashMap::new(),
+            manual_game_dir: "".to_string(),
+            install_checks: Vec::new(),
+            scan_events: None,
+            scan_progress: "".to_string(),
+            game_version: None,
+            app_manifest: None,
+            launch_warnings: Vec::new(),
+
+            convar_dump_path: "".to_string(),
+            catalog_window_open: false,
+            catalog_entries: Vec::new(),
+            bundled_catalog: None,
+            catalog_diff: None,
+            catalog_status: "".to_string(),
+            catalog_save_path: "".to_string(),
+
+            show_login_token: false,
+
+            settings_file_window_open: false,
+            settings_file_path: "".to_string(),
+            export_login_token: false,
+            settings_file_status: "".to_string(),
+            confirm_settings_export: false,
+            confirm_settings_import: false,
+
+            steamcmd_path: "".to_string(),
+            steamcmd_install_dir: "".to_string(),
+            steamcmd_validate: true,
+            steamcmd_window_open: false,
+            steamcmd_events: None,
+            steamcmd_progress: None,
+            steamcmd_output: Vec::new(),
+            steamcmd_status: "".to_string(),
+
+            installations: Vec::new(),
+            installations_window_open: false,
+            installation_details: HashMap::new(),
+            new_installation_label: "".to_string(),
+            new_installation_path: "".to_string(),
+
+            watchdog_settings: WatchdogSettings::default(),
+            watchdog: Watchdog::default(),
+            watchdog_window_open: false,
+        }
+    }
+}
+
+impl CS2ServerPrestarterApp {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let style = Style {
+            visuals: Visuals::dark(),
+            ..Style::default()
+        };
+        cc.egui_ctx.set_style(style);
+        let mut app: Self = match cc.storage {
+            Some(storage) => {
+                let mut app: Self = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+                // same top-level keys map onto `ServerSettings`, everything else is ignored
+                let legacy: Option<LegacyLayout> = eframe::get_value(storage, eframe::APP_KEY);
+                if legacy.is_some_and(|l| l.is_legacy()) {
+                    if let Some(settings) = eframe::get_value(storage, eframe::APP_KEY) {
+                        log::info!("Migrating settings saved by previous version");
+                        app.settings = settings;
+                    }
+                }
+                app
+            }
+            None => Default::default(),
+        };
+        if let Some(cached) = scanner::load_cache(&app.scan_request()) {
+            app.apply_scan(cached);
+        }
+        app.start_scan();
+        app.restart_log_receiver();
+        return app;
+    }
 
-    // 1/0
-    mp_autokick: bool,
+    // Falls back to CS2 layout so the UI keeps working while unsupported app is selected
+    fn game(&self) -> &'static dyn GameDefinition {
+        return game::definition_for_app(self.app_id).unwrap_or(&CounterStrike2);
+    }
+
+    fn scan_request(&self) -> scanner::ScanRequest {
+        let game_path_override =
+            match installations::find(&self.installations, &self.settings.installation) {
+                Some(installation) => installation.path.clone(),
+                None => self.game_path_str.clone(),
+            };
+        return scanner::ScanRequest {
+            app_id: self.app_id,
+            game_path_override,
+        };
+    }
+
+    // Resolves game path from manually chosen folder or selected Steam app in background
+    fn start_scan(&mut self) {
+        self.scan_progress = String::from("Starting scan");
+        self.scan_events = Some(scanner::start(self.scan_request()));
+    }
+
+    fn poll_scan(&mut self, ctx: &egui::Context) {
+        let mut finished: Option<scanner::ScanOutcome> = None;
+
+        if let Some(events) = &self.scan_events {
+            loop {
+                match events.try_recv() {
+                    Ok(scanner::ScanEvent::Progress(message)) => self.scan_progress = message,
+                    Ok(scanner::ScanEvent::Finished(outcome)) => {
+                        finished = Some(*outcome);
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => {
+                        ctx.request_repaint_after(Duration::from_millis(100));
+                        return;
+                    }
+                    Err(TryRecvError::Disconnected) => break,
+                }
+            }
+        }
+
+        self.scan_events = None;
+        if let Some(outcome) = finished {
+            self.apply_scan(outcome);
+        }
+    }
+
+    fn apply_scan(&mut self, outcome: scanner::ScanOutcome) {
+        self.ready = outcome.state == steam::ReadyState::Ready;
+        self.error_title = match outcome.state {
+            steam::ReadyState::Ready => "".to_string(),
+            steam::ReadyState::NoGame => "Game not found".to_string(),
+            steam::ReadyState::NoSteam => "Steam not found".to_string(),
+        };
+        self.game_path = outcome.game_path;
+        self.available_maps = outcome.maps;
+        self.install_checks = outcome.install_checks;
+        self.discovery_problems = outcome.problems;
+        self.available_steam_apps = outcome.apps;
+        self.game_version = outcome.version;
+        self.app_manifest = outcome.manifest;
+    }
+
+    // Asks for confirmation first if Steam reports install as outdated or broken
+    fn request_launch(&mut self) {
+        self.app_manifest = steam::AppManifest::for_install_dir(&self.game_path, &self.app_id);
+        self.launch_warnings = match &self.app_manifest {
+            Some(manifest) => manifest.problems(),
+            None => Vec::new(),
+        };
+
+        if self.launch_warnings.is_empty() {
+            self.launch_server();
+        }
+    }
+
+    fn launch_server(&mut self) {
+        self.launch_log_address = self.log_receiver.as_ref().map(|r| r.local_url());
+        self.server_args = launch::build_args(&self.settings, self.launch_log_address.as_deref());
+        self.server_envs = launch::build_envs(&self.settings);
+        self.admin_plugin = game::has_admin_plugin(self.game(), &self.game_path);
 
-    //     0 - default, only in buy zones
-    //     1 - buy anywhere for all teams
-    //     2 - buy anywhere for terrorists only
-    //     3 - buy anywhere for counter-terrorists only
-    mp_buy_anywhere: u8,
+        if !self.settings.motd.is_empty() {
+            if let Err(err) = game::write_motd(self.game(), &self.game_path, &self.settings.motd) {
+                log::warn!("Unable to write motd.txt: {err}");
+            }
+        }
 
-    // seconds
-    mp_buytime: u32,
+        self.watchdog.reset();
+        if let Err(err) = self.spawn_server() {
+            log::warn!("Unable to start server: {err}");
+        }
+    }
 
-    // seconds
-    mp_c4timer: u32,
+    // Starts server with arguments of last launch
+    fn spawn_server(&mut self) -> Result<(), String> {
+        match game::create_server_process(
+            self.game(),
+            &self.game_path,
+            self.server_args.clone(),
+            self.server_envs.clone(),
+        ) {
+            Ok(child) => {
+                self.server_process = Some(ServerProcess::new(child));
+                self.scoreboard = Scoreboard::default();
+                self.readiness = Readiness::started();
+                self.registered_log_address = self.launch_log_address.clone();
+                self.session_log = match session_log::log_dir() {
+                    Some(dir) => SessionLog::create(&dir, &self.log_rotation)
+                        .map_err(|err| log::warn!("Unable to create session log: {err}"))
+                        .ok(),
+                    None => None,
+                };
+                // receiver may have been restarted since launch
+                self.register_log_address();
+                return Ok(());
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+    }
 
-    // seconds
-    mp_freezetime: u32,
+    // Stopping by user is never treated as crash
+    fn stop_server(&mut self) {
+        self.watchdog.reset();
+        if let Some(mut process) = self.server_process.take() {
+            if let Err(err) = process.kill() {
+                log::warn!("Unable to stop server: {err}");
+            }
+        }
+        self.end_session();
+    }
+
+    // Drops state tied to server process that is gone
+    fn end_session(&mut self) {
+        self.readiness.stopped();
+        self.session_log = None;
+        self.recording_demo = None;
+        self.roster = PlayerRoster::default();
+        self.registered_log_address = None;
+    }
+
+    // True when running server sends its logs to current receiver
+    fn receiving_server_logs(&self) -> bool {
+        return match &self.log_receiver {
+            Some(receiver) => self.registered_log_address == Some(receiver.local_url()),
+            None => false,
+        };
+    }
+
+    // Points running server to current receiver, every receiver start has new URL
+    fn register_log_address(&mut self) {
+        let Some(url) = self.log_receiver.as_ref().map(|r| r.local_url()) else {
+            return;
+        };
+        if self.server_process.is_none() || self.registered_log_address.as_ref() == Some(&url) {
+            return;
+        }
+        if let Some(previous) = self.registered_log_address.take() {
+            let _ = self.send_server_command(&format!("logaddress_del_http \"{previous}\""));
+        }
+        match self.send_server_command(&format!("logaddress_add_http \"{url}\"")) {
+            Ok(_) => self.registered_log_address = Some(url),
+            Err(err) => log::warn!("Unable to register log receiver: {err}"),
+        }
+    }
+
+    fn poll_server(&mut self, ctx: &egui::Context) {
+        let mut events: Vec<MatchEvent> = Vec::new();
+        let mut bind_failed = false;
+
+        // stdout is parsed until server sends the same events to receiver
+        let receiving = self.receiving_server_logs();
+        if let Some(process) = &mut self.server_process {
+            let lines = process.read_lines();
+            events = lines
+                .iter()
+                .filter_map(|line| match_events::parse_line(line))
+                .collect();
+            if !receiving {
+                for event in &events {
+                    self.scoreboard.apply(event);
+                }
+                self.roster.observe(&events);
+            }
+            if let Some(session_log) = &mut self.session_log {
+                let written = lines
+                    .iter()
+                    .try_for_each(|line| session_log.write_line(line))
+                    .and_then(|_| session_log.flush());
+                if let Err(err) = written {
+                    log::warn!("Unable to write session log: {err}");
+                    self.session_log = None;
+                }
+            }
+            for line in lines {
+                self.readiness.feed(&line);
+                self.roster.feed(&line);
+                self.server_output.push(line);
+            }
+            if self.server_output.len() > SERVER_OUTPUT_LINES {
+                let excess = self.server_output.len() - SERVER_OUTPUT_LINES;
+                self.server_output.drain(..excess);
+            }
+            // readiness only stops by itself on bind failure
+            bind_failed = self.readiness.state == ServerState::Stopped;
+
+            match process.try_wait() {
+                Ok(None) => {}
+                Ok(Some(status)) => {
+                    let uptime = process.started_at.elapsed();
+                    self.server_process = None;
+                    self.end_session();
+                    log::warn!("Server exited with {status} after {}s", uptime.as_secs());
+                    self.watchdog
+                        .on_exit(&self.watchdog_settings, &status, uptime);
+                }
+                Err(err) => log::warn!("Unable to check server process: {err}"),
+            }
+        }
+        // only events echoed by own server drive console commands
+        self.record_demos(&events);
+
+        // process keeps running without a usable port, so it is treated as failed start
+        if bind_failed {
+            if let Some(mut process) = self.server_process.take() {
+                if let Err(err) = process.kill() {
+                    log::warn!("Unable to stop server: {err}");
+                }
+                self.end_session();
+                let failure = self.readiness.failure.clone().unwrap_or_default();
+                log::warn!("Server failed to start: {failure}");
+                self.watchdog
+                    .on_spawn_failure(&self.watchdog_settings, &failure);
+            }
+        }
+
+        if self.watchdog.take_due() {
+            log::info!("Restarting server, attempt {}", self.watchdog.attempts());
+            if let Err(err) = self.spawn_server() {
+                log::warn!("Unable to restart server: {err}");
+                self.watchdog
+                    .on_spawn_failure(&self.watchdog_settings, &err);
+            }
+        }
+
+        if self.server_process.is_some() || self.watchdog.pending().is_some() {
+            ctx.request_repaint_after(Duration::from_millis(500));
+        }
+    }
+
+    fn show_console_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.console_window_open;
+
+        egui::Window::new("Server console")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("State: {}", self.readiness.state));
+                    if ui
+                        .button("Clear")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        self.server_output.clear();
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in &self.server_output {
+                            ui.monospace(line);
+                        }
+                    });
+            });
+
+        self.console_window_open = open;
+    }
+
+    // Only managed server has console input, servers started elsewhere can't be controlled
+    fn send_server_command(&mut self, command: &str) -> io::Result<()> {
+        let Some(process) = &mut self.server_process else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "server is not running",
+            ));
+        };
+        log::info!("Sending to server: {command}");
+        return process.send_command(command);
+    }
+
+    // Drives `tv_record` through console of managed server, servers started elsewhere are left alone
+    fn record_demos(&mut self, events: &[MatchEvent]) {
+        let settings = &self.settings;
+        if !settings.tv_enable || settings.tv_autorecord || !settings.auto_record_demos {
+            return;
+        }
+        let Some(process) = &mut self.server_process else {
+            return;
+        };
+
+        for event in events {
+            let mut commands: Vec<String> = Vec::new();
+            match event {
+                MatchEvent::MatchStart { map } => {
+                    // match restart begins new demo
+                    if self.recording_demo.is_some() {
+                        commands.push(String::from("tv_stoprecord"));
+                    }
+                    let name = demos::demo_name(
+                        SystemTime::now(),
+                        map,
+                        &settings.mp_teamname_1,
+                        &settings.mp_teamname_2,
+                    );
+                    commands.push(format!("tv_record \"{name}\""));
+                    self.recording_demo = Some(name);
+                }
+                MatchEvent::MatchEnd { .. } if self.recording_demo.is_some() => {
+                    commands.push(String::from("tv_stoprecord"));
+                    self.recording_demo = None;
+                }
+                _ => {}
+            }
+
+            for command in commands {
+                if let Err(err) = process.send_command(&command) {
+                    log::warn!("Unable to send {command}: {err}");
+                }
+            }
+        }
+    }
+
+    fn restart_log_receiver(&mut self) {
+        // previous receiver has to release the port first
+        self.log_receiver = None;
+        if !self.log_receiver_settings.enabled {
+            self.log_receiver_status = String::from("Disabled");
+            return;
+        }
+
+        match LogReceiver::start(
+            self.log_receiver_settings.port,
+            self.log_receiver_settings.allow_lan,
+        ) {
+            Ok(receiver) => {
+                self.log_receiver_status = match receiver.allow_lan {
+                    true => format!("Listening on port {} for LAN servers", receiver.port),
+                    false => format!("Listening on port {} for local servers", receiver.port),
+                };
+                self.log_receiver = Some(receiver);
+                self.register_log_address();
+            }
+            Err(err) => {
+                self.log_receiver_status = format!(
+                    "Unable to listen on port {}: {err}",
+                    self.log_receiver_settings.port
+                );
+            }
+        }
+    }
+
+    fn poll_log_receiver(&mut self, ctx: &egui::Context) {
+        if let Some(receiver) = &mut self.log_receiver {
+            let (_, events) = self.scoreboard.consume(receiver);
+            self.roster.observe(&events);
+            ctx.request_repaint_after(Duration::from_millis(500));
+        }
+    }
+
+    fn show_log_receiver_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.log_receiver_window_open;
+        let mut restart = false;
+
+        egui::Window::new("Log receiver")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("log_receiver")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Receive HTTP logs").on_hover_text(
+                            "Servers started from this app register receiver automatically",
+                        );
+                        ui.checkbox(&mut self.log_receiver_settings.enabled, "")
+                            .on_hover_cursor(CursorIcon::PointingHand);
+                        ui.end_row();
+
+                        ui.label("Port");
+                        ui.add(
+                            egui::DragValue::new(&mut self.log_receiver_settings.port)
+                                .clamp_range(1024..=65535),
+                        );
+                        ui.end_row();
+
+                        ui.label("Accept from LAN").on_hover_text(
+                            "Servers on other machines can send logs to address with session token",
+                        );
+                        ui.checkbox(&mut self.log_receiver_settings.allow_lan, "")
+                            .on_hover_cursor(CursorIcon::PointingHand);
+                        ui.end_row();
+                    });
+
+                if ui
+                    .button("Apply")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    restart = true;
+                }
+                ui.label(&self.log_receiver_status);
+
+                if let Some(receiver) = &self.log_receiver {
+                    ui.separator();
+                    ui.label("For servers started elsewhere run in their console:");
+                    let mut urls = vec![receiver.local_url()];
+                    urls.extend(receiver.lan_url());
+                    for url in urls {
+                        let command = format!("logaddress_add_http \"{url}\"");
+                        ui.horizontal(|ui| {
+                            ui.monospace(&command);
+                            if ui
+                                .button("Copy")
+                                .on_hover_cursor(CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                ui.output_mut(|o| o.copied_text = command.clone());
+                            }
+                        });
+                    }
+                }
+            });
+
+        if restart {
+            self.restart_log_receiver();
+        }
+        self.log_receiver_window_open = open;
+    }
+
+    fn refresh_demos(&mut self) {
+        let dir = Path::new(&self.game_path).join(self.game().content_dir());
+        self.demo_files = demos::list(&dir);
+        if let Some(path) = &self.selected_demo {
+            if !self.demo_files.iter().any(|d| &d.path == path) {
+                self.selected_demo = None;
+            }
+        }
+    }
+
+    fn show_demos_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.demos_window_open;
+        let mut refresh = false;
+        let mut selected: Option<PathBuf> = None;
+        let mut rename = false;
+        let mut move_to = false;
+        let mut delete = false;
+        let mut play: Option<String> = None;
+
+        egui::Window::new("Demos")
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                if ui
+                    .button("Refresh")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    refresh = true;
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .id_source("demo_files")
+                    .max_height(280.0)
+                    .show(ui, |ui| {
+                        if self.demo_files.is_empty() {
+                            ui.label("No demos recorded yet");
+                        }
+                        egui::Grid::new("demo_files")
+                            .num_columns(5)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["Name", "Map", "Duration", "Size", "Recorded"] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for demo in &self.demo_files {
+                                    let is_selected =
+                                        self.selected_demo.as_ref() == Some(&demo.path);
+                                    let mut name = ui.selectable_label(is_selected, &demo.name);
+                                    match &demo.header {
+                                        Some(header) => {
+                                            name = name.on_hover_text(format!(
+                                                "{}, build {}",
+                                                header.server_name, header.build
+                                            ));
+                                            ui.label(&header.map);
+                                            ui.label(match header.playback_secs {
+                                                Some(secs) => {
+                                                    ConvarDuration::from_secs(secs as u32)
+                                                        .to_mm_ss()
+                                                }
+                                                None => String::from("unknown"),
+                                            });
+                                        }
+                                        None => {
+                                            name = name.on_hover_text("Unable to read demo header");
+                                            ui.label("?");
+                                            ui.label("?");
+                                        }
+                                    }
+                                    if name.clicked() {
+                                        selected = Some(demo.path.clone());
+                                    }
+                                    ui.label(format!("{} MB", demo.size / (1024 * 1024)));
+                                    ui.label(utils::format_utc(demo.modified));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.separator();
+
+                let Some(path) = self.selected_demo.clone() else {
+                    ui.label("Select a demo to manage it");
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Play")
+                        .on_hover_text("Opens demo in game client")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                        play = Some(self.game().play_demo_url(&name));
+                    }
+                    if ui
+                        .add_enabled(!self.demo_delete_pending, egui::Button::new("Delete"))
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        self.demo_delete_pending = true;
+                    }
+                });
+                if self.demo_delete_pending {
+                    ui.horizontal(|ui| {
+                        let name = path.file_name().unwrap_or_default().to_string_lossy();
+                        ui.colored_label(Color32::YELLOW, format!("Really delete {name}?"));
+                        if ui
+                            .button("Confirm")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            delete = true;
+                            self.demo_delete_pending = false;
+                        }
+                        if ui
+                            .button("Cancel")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            self.demo_delete_pending = false;
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Rename to");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.demo_new_name).desired_width(200.0),
+                    );
+                    if ui
+                        .add_enabled(!self.demo_new_name.is_empty(), egui::Button::new("Rename"))
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        rename = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Move to");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.demo_move_dir).desired_width(200.0),
+                    );
+                    if ui
+                        .add_enabled(!self.demo_move_dir.is_empty(), egui::Button::new("Move"))
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        move_to = true;
+                    }
+                });
+                if !self.demo_status.is_empty() {
+                    ui.label(&self.demo_status);
+                }
+            });
+
+        if let Some(path) = selected {
+            self.demo_new_name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            self.demo_status.clear();
+            self.demo_delete_pending = false;
+            self.selected_demo = Some(path);
+        }
+        if let Some(url) = play {
+            ctx.open_url(egui::OpenUrl::same_tab(url));
+        }
+        if let Some(path) = self.selected_demo.clone() {
+            let result = if rename {
+                Some(
+                    demos::rename(&path, &self.demo_new_name)
+                        .map(|p| format!("Renamed to {}", p.display())),
+                )
+            } else if move_to {
+                Some(
+                    demos::move_to(&path, Path::new(&self.demo_move_dir))
+                        .map(|p| format!("Moved to {}", p.display())),
+                )
+            } else if delete {
+                Some(fs::remove_file(&path).map(|_| format!("Deleted {}", path.display())))
+            } else {
+                None
+            };
+            if let Some(result) = result {
+                self.demo_status = match result {
+                    Ok(status) => status,
+                    Err(err) => format!("Unable to update {}: {err}", path.display()),
+                };
+                refresh = true;
+            }
+        }
+        if refresh {
+            self.refresh_demos();
+        }
+        self.demos_window_open = open;
+    }
+
+    // Asks managed server for `status` while players window is open, output is read by `poll_server`
+    fn poll_players(&mut self) {
+        if !self.players_window_open || self.server_process.is_none() {
+            return;
+        }
+        if self
+            .status_requested_at
+            .is_some_and(|at| at.elapsed() < STATUS_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.status_requested_at = Some(Instant::now());
+        if let Err(err) = self.send_server_command("status") {
+            self.players_status = format!("Unable to request status: {err}");
+        }
+    }
+
+    fn show_players_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.players_window_open;
+        let mut action: Option<(i32, String, PlayerAction)> = None;
+        let running = self.server_process.is_some();
+        let admin_plugin = self.admin_plugin;
+
+        egui::Window::new("Players")
+            .open(&mut open)
+            .default_width(620.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    match self.roster.updated_at {
+                        Some(at) => ui.label(format!(
+                            "{} players, updated {}s ago",
+                            self.roster.players.len(),
+                            at.elapsed().as_secs()
+                        )),
+                        None if running => ui.label("Waiting for status"),
+                        None => ui.label("Start server from here to manage players"),
+                    };
+                    ui.label("Ban for");
+                    ui.add(
+                        egui::DragValue::new(&mut self.ban_minutes)
+                            .clamp_range(0..=525600)
+                            .suffix(" min"),
+                    )
+                    .on_hover_text("0 bans permanently");
+                });
+                if !self.players_status.is_empty() {
+                    ui.label(&self.players_status);
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("players")
+                            .num_columns(6)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["#", "Player", "SteamID", "Team", "Ping", ""] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for player in &self.roster.players {
+                                    let status = &player.status;
+                                    ui.label(status.user_id.to_string());
+                                    ui.label(&status.name).on_hover_text(format!(
+                                        "{}, connected {}, loss {}",
+                                        status.state, status.connected, status.loss
+                                    ));
+                                    ui.label(&player.steam_id);
+                                    ui.colored_label(
+                                        team_color(player.team),
+                                        player.team.short_name(),
+                                    );
+                                    ui.label(status.ping.to_string());
+
+                                    let mut actions = vec![
+                                        PlayerAction::Kick,
+                                        PlayerAction::Ban {
+                                            minutes: self.ban_minutes,
+                                        },
+                                        PlayerAction::Move(Team::CounterTerrorist),
+                                        PlayerAction::Move(Team::Terrorist),
+                                        PlayerAction::Move(Team::Spectator),
+                                        PlayerAction::Mute,
+                                    ];
+                                    // bots can't be banned or muted
+                                    if status.bot {
+                                        actions.retain(|a| {
+                                            !matches!(
+                                                a,
+                                                PlayerAction::Ban { .. } | PlayerAction::Mute
+                                            )
+                                        });
+                                    }
+                                    ui.add_enabled_ui(running, |ui| {
+                                        ui.menu_button("Actions", |ui| {
+                                            for candidate in actions {
+                                                let available =
+                                                    admin_plugin || !candidate.needs_plugin();
+                                                let button = ui
+                                                    .add_enabled(
+                                                        available,
+                                                        egui::Button::new(candidate.label()),
+                                                    )
+                                                    .on_disabled_hover_text(
+                                                        "Needs CS2-SimpleAdmin plugin",
+                                                    );
+                                                if button.clicked() {
+                                                    action = Some((
+                                                        status.user_id,
+                                                        status.name.clone(),
+                                                        candidate,
+                                                    ));
+                                                    ui.close_menu();
+                                                }
+                                            }
+                                        });
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if let Some((user_id, name, action)) = action {
+            let commands = action.commands(user_id);
+            let sent = commands
+                .iter()
+                .try_for_each(|command| self.send_server_command(command));
+            self.players_status = match sent {
+                Ok(_) => format!("{}: {name}", action.label()),
+                Err(err) => format!("Unable to {} {name}: {err}", action.label().to_lowercase()),
+            };
+            // show result of action without waiting for next refresh
+            self.status_requested_at = None;
+        }
+        self.players_window_open = open;
+    }
+
+    fn show_match_control_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.match_control_window_open;
+        let mut action: Option<MatchAction> = None;
+        let running = self.server_process.is_some();
+
+        egui::Window::new("Match control")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                if !running {
+                    ui.label("Start server from here to control the match");
+                }
+                ui.add_enabled_ui(running, |ui| {
+                    let mut button = |ui: &mut egui::Ui, candidate: MatchAction| {
+                        if ui
+                            .button(candidate.label())
+                            .on_hover_text(candidate.command())
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            action = Some(candidate);
+                        }
+                    };
+
+                    ui.horizontal_wrapped(|ui| {
+                        button(ui, MatchAction::RestartGame);
+                        button(ui, MatchAction::EndWarmup);
+                        button(ui, MatchAction::Pause);
+                        button(ui, MatchAction::Unpause);
+                    });
+                    ui.horizontal_wrapped(|ui| {
+                        button(ui, MatchAction::TacticalTimeout(Team::CounterTerrorist));
+                        button(ui, MatchAction::TacticalTimeout(Team::Terrorist));
+                        button(ui, MatchAction::TechnicalTimeout(Team::CounterTerrorist));
+                        button(ui, MatchAction::TechnicalTimeout(Team::Terrorist));
+                    });
+                    ui.horizontal_wrapped(|ui| {
+                        button(ui, MatchAction::SwapTeams);
+                        button(ui, MatchAction::ScrambleTeams);
+                        button(ui, MatchAction::KickBots);
+                    });
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("control_map")
+                            .selected_text(&self.control_map)
+                            .show_ui(ui, |ui| {
+                                for one_map in &self.available_maps {
+                                    ui.selectable_value(
+                                        &mut self.control_map,
+                                        one_map.to_string(),
+                                        one_map,
+                                    );
+                                }
+                            });
+                        if ui
+                            .add_enabled(
+                                !self.control_map.is_empty(),
+                                egui::Button::new("Change map"),
+                            )
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            action = Some(MatchAction::ChangeLevel(self.control_map.clone()));
+                        }
+                    });
+                });
+                if !self.match_control_status.is_empty() {
+                    ui.label(&self.match_control_status);
+                }
+            });
+
+        if let Some(action) = action {
+            self.match_control_status = match self.send_server_command(&action.command()) {
+                Ok(_) => format!("{}: sent {}", action.label(), action.command()),
+                Err(err) => format!("Unable to send {}: {err}", action.command()),
+            };
+        }
+        self.match_control_window_open = open;
+    }
+
+    fn refresh_round_backups(&mut self) {
+        let dir = Path::new(&self.game_path).join(self.game().content_dir());
+        self.round_backups = round_backups::list(&dir);
+    }
+
+    fn show_backups_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.backups_window_open;
+        let mut refresh = false;
+        let mut restore: Option<String> = None;
+        let running = self.server_process.is_some();
+
+        egui::Window::new("Round backups")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Refresh")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        refresh = true;
+                    }
+                    ui.label("Written every round when mp_backup_round_auto is on");
+                });
+                if !self.backup_status.is_empty() {
+                    ui.label(&self.backup_status);
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        if self.round_backups.is_empty() {
+                            ui.label("No round backups found");
+                        }
+                        egui::Grid::new("round_backups")
+                            .num_columns(5)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["File", "Map", "Round", "Score", "Saved"] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for backup in &self.round_backups {
+                                    ui.label(&backup.name)
+                                        .on_hover_text(backup.path.display().to_string());
+                                    match &backup.info {
+                                        Some(info) => {
+                                            ui.label(&info.map);
+                                            ui.label(info.round.to_string());
+                                            ui.label(format!(
+                                                "{} : {}",
+                                                info.team1_score, info.team2_score
+                                            ));
+                                            ui.label(&info.saved_at);
+                                        }
+                                        None => {
+                                            ui.label("?");
+                                            ui.label("?");
+                                            ui.label("?");
+                                            ui.label(format!(
+                                                "{} UTC",
+                                                utils::format_utc(backup.modified)
+                                            ));
+                                        }
+                                    }
+                                    if ui
+                                        .add_enabled(running, egui::Button::new("Restore"))
+                                        .on_hover_text("Loads this round on running server")
+                                        .on_disabled_hover_text(
+                                            "Server started from here is not running",
+                                        )
+                                        .on_hover_cursor(CursorIcon::PointingHand)
+                                        .clicked()
+                                    {
+                                        restore = Some(round_backups::restore_command(backup));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if let Some(command) = restore {
+            self.backup_status = match self.send_server_command(&command) {
+                Ok(_) => format!("Sent {command}"),
+                Err(err) => format!("Unable to restore: {err}"),
+            };
+        }
+        if refresh {
+            self.refresh_round_backups();
+        }
+        self.backups_window_open = open;
+    }
+
+    fn show_settings_file_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.settings_file_window_open;
+        let mut export = false;
+        let mut import = false;
+        let confirming = self.confirm_settings_export || self.confirm_settings_import;
+
+        egui::Window::new("Export/import settings")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File");
+                    // path stays as confirmed until action is done
+                    ui.add_enabled(
+                        !confirming,
+                        egui::TextEdit::singleline(&mut self.settings_file_path)
+                            .hint_text("settings.json")
+                            .desired_width(280.0),
+                    );
+                });
+                ui.checkbox(&mut self.export_login_token, "Include login token")
+                    .on_hover_text("Anyone with the file can run servers under your account")
+                    .on_hover_cursor(CursorIcon::PointingHand);
+                ui.add_enabled_ui(!self.settings_file_path.is_empty() && !confirming, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("Export")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            if Path::new(&self.settings_file_path).exists() {
+                                self.confirm_settings_export = true;
+                            } else {
+                                export = true;
+                            }
+                        }
+                        if ui
+                            .button("Import")
+                            .on_hover_text("Replaces current settings")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            self.confirm_settings_import = true;
+                        }
+                    });
+                });
+                if confirming {
+                    let question = match self.confirm_settings_export {
+                        true => format!("Really overwrite {}?", self.settings_file_path),
+                        false => format!(
+                            "Really replace all current settings with {}?",
+                            self.settings_file_path
+                        ),
+                    };
+                    ui.horizontal(|ui| {
+                        ui.colored_label(Color32::YELLOW, question);
+                        if ui
+                            .button("Confirm")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            export = self.confirm_settings_export;
+                            import = self.confirm_settings_import;
+                            self.confirm_settings_export = false;
+                            self.confirm_settings_import = false;
+                        }
+                        if ui
+                            .button("Cancel")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            self.confirm_settings_export = false;
+                            self.confirm_settings_import = false;
+                        }
+                    });
+                }
+                if !self.settings_file_status.is_empty() {
+                    ui.label(&self.settings_file_status);
+                }
+            });
+
+        let path = Path::new(&self.settings_file_path);
+        if export {
+            let written = self
+                .settings
+                .export(self.export_login_token)
+                .map_err(io::Error::from)
+                .and_then(|content| fs::write(path, content));
+            self.settings_file_status = match written {
+                Ok(_) => format!("Exported to {}", path.display()),
+                Err(err) => format!("Unable to export: {err}"),
+            };
+        }
+        if import {
+            let imported = fs::read_to_string(path)
+                .and_then(|content| self.settings.import(&content).map_err(io::Error::from));
+            self.settings_file_status = match imported {
+                Ok(settings) => {
+                    self.settings = settings;
+                    format!("Imported from {}", path.display())
+                }
+                Err(err) => format!("Unable to import: {err}"),
+            };
+        }
+        self.settings_file_window_open = open;
+    }
+
+    fn refresh_past_matches(&mut self) {
+        self.past_matches = match match_export::matches_dir() {
+            Some(dir) => match_export::list(&dir),
+            None => Vec::new(),
+        };
+        self.selected_match = None;
+    }
+
+    // Saves summary of match and player stats once game over line is seen
+    fn save_finished_match(&mut self) {
+        let Some(scoreboard) = self.scoreboard.take_finished() else {
+            return;
+        };
+        let summary = MatchSummary::from_scoreboard(&scoreboard, SystemTime::now());
+
+        match match_export::matches_dir() {
+            Some(dir) => match match_export::export(&dir, &summary) {
+                Ok(path) => log::info!("Match exported to {}", path.display()),
+                Err(err) => log::warn!("Unable to export match: {err}"),
+            },
+            None => log::warn!("Unable to export match: no data directory"),
+        }
+        if let Err(err) = StatsDb::open().and_then(|mut db| db.record_match(&summary)) {
+            log::warn!("Unable to save match stats: {err}");
+        }
+
+        if self.leaderboard_window_open {
+            self.refresh_leaderboard();
+        }
+        if self.matches_window_open {
+            self.refresh_past_matches();
+        }
+    }
+
+    fn refresh_leaderboard(&mut self) {
+        let valid = |date: &str| date.is_empty() || is_iso_date(date);
+        if !valid(&self.leaderboard_from) || !valid(&self.leaderboard_to) {
+            self.leaderboard_status = String::from("Dates should look like 2024-01-31");
+            return;
+        }
+
+        let result = StatsDb::open()
+            .and_then(|db| db.leaderboard(&self.leaderboard_from, &self.leaderboard_to));
+        match result {
+            Ok(leaderboard) => {
+                self.leaderboard_status = format!("{} players", leaderboard.len());
+                self.leaderboard = leaderboard;
+            }
+            Err(err) => self.leaderboard_status = format!("Unable to read stats: {err}"),
+        }
+    }
+
+    fn show_leaderboard_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.leaderboard_window_open;
+        let mut refresh = false;
+
+        egui::Window::new("Leaderboard")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("From");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.leaderboard_from)
+                            .hint_text("YYYY-MM-DD")
+                            .desired_width(90.0),
+                    );
+                    ui.label("To");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.leaderboard_to)
+                            .hint_text("YYYY-MM-DD")
+                            .desired_width(90.0),
+                    );
+                    if ui
+                        .button("Apply")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        refresh = true;
+                    }
+                });
+                ui.label(&self.leaderboard_status);
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("leaderboard")
+                            .num_columns(6)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in
+                                    ["Player", "Matches", "K/D", "Win rate", "Maps", "Best map"]
+                                {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for entry in &self.leaderboard {
+                                    ui.label(&entry.name).on_hover_text(&entry.steam_id);
+                                    ui.label(entry.matches.to_string());
+                                    ui.label(format!("{:.2}", entry.kd()))
+                                        .on_hover_text(format!(
+                                            "{} kills, {} deaths, {} assists",
+                                            entry.kills, entry.deaths, entry.assists
+                                        ));
+                                    ui.label(format!("{:.0}%", entry.win_rate() * 100.0));
+                                    ui.label(entry.maps_played.to_string());
+                                    ui.label(&entry.best_map);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if refresh {
+            self.refresh_leaderboard();
+        }
+        self.leaderboard_window_open = open;
+    }
+
+    fn show_matches_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.matches_window_open;
+        let mut refresh = false;
+        let mut selected = self.selected_match;
+
+        egui::Window::new("Past matches")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                if ui
+                    .button("Refresh")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    refresh = true;
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .id_source("past_matches")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        if self.past_matches.is_empty() {
+                            ui.label("No finished matches yet");
+                        }
+                        for (index, exported) in self.past_matches.iter().enumerate() {
+                            let summary = &exported.summary;
+                            let label = format!(
+                                "{}  {} ({})  CT {} : {} T",
+                                summary.finished_at,
+                                summary.map,
+                                summary.mode,
+                                summary.ct_score,
+                                summary.t_score
+                            );
+                            if ui
+                                .selectable_label(selected == Some(index), label)
+                                .clicked()
+                            {
+                                selected = Some(index);
+                            }
+                        }
+                    });
+
+                let Some(exported) = selected.and_then(|i| self.past_matches.get(i)) else {
+                    return;
+                };
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} rounds, {} min",
+                        exported.summary.rounds.len(),
+                        exported.summary.minutes
+                    ));
+                    if ui
+                        .button("Open folder")
+                        .on_hover_text(exported.dir.display().to_string())
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        if cfg!(target_os = "windows") {
+                            let _ = Command::new("explorer").args([&exported.dir]).spawn();
+                        };
+                    }
+                });
+
+                egui::ScrollArea::vertical()
+                    .id_source("past_match_players")
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("past_match_players")
+                            .num_columns(7)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["Player", "Team", "K", "D", "A", "HS", "ADR"] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for player in &exported.summary.players {
+                                    ui.label(&player.name).on_hover_text(&player.steam_id);
+                                    ui.label(&player.team);
+                                    ui.label(player.kills.to_string());
+                                    ui.label(player.deaths.to_string());
+                                    ui.label(player.assists.to_string());
+                                    ui.label(player.headshots.to_string());
+                                    ui.label(format!("{:.0}", player.adr));
+                                    ui.end_row();
+                                }
+                            });
+
+                        ui.add_space(8.0);
+                        for round in &exported.summary.rounds {
+                            ui.label(format!(
+                                "Round {}: {} won, {}",
+                                round.number, round.winner, round.reason
+                            ));
+                        }
+                    });
+            });
+
+        self.selected_match = selected;
+        if refresh {
+            self.refresh_past_matches();
+        }
+        self.matches_window_open = open;
+    }
+
+    fn show_scoreboard_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.scoreboard_window_open;
+
+        egui::Window::new("Scoreboard")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                let scoreboard = &self.scoreboard;
+                ui.horizontal(|ui| {
+                    ui.heading(format!(
+                        "CT {} : {} T",
+                        scoreboard.ct_score, scoreboard.t_score
+                    ));
+                    if !scoreboard.map.is_empty() {
+                        ui.label(&scoreboard.map);
+                    }
+                    if scoreboard.finished {
+                        ui.label("Match is over");
+                    }
+                });
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 2.0;
+                    for round in &scoreboard.rounds {
+                        let color = team_color(round.winner);
+                        let mark = if round.bomb_defused {
+                            "D"
+                        } else if round.bomb_planted {
+                            "B"
+                        } else {
+                            " "
+                        };
+                        ui.label(
+                            egui::RichText::new(mark)
+                                .monospace()
+                                .background_color(color)
+                                .color(Color32::BLACK),
+                        )
+                        .on_hover_text(format!(
+                            "Round {}: {} won",
+                            round.number,
+                            round.winner.short_name()
+                        ));
+                    }
+                });
+                ui.separator();
+
+                for team in [Team::CounterTerrorist, Team::Terrorist] {
+                    let players = scoreboard.team(team);
+                    if players.is_empty() {
+                        continue;
+                    }
+                    egui::Grid::new(team.short_name())
+                        .num_columns(6)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.colored_label(team_color(team), team.short_name());
+                            for header in ["K", "D", "A", "HS", "ADR"] {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+
+                            for player in players {
+                                ui.label(&player.name);
+                                ui.label(player.kills.to_string());
+                                ui.label(player.deaths.to_string());
+                                ui.label(player.assists.to_string());
+                                ui.label(player.headshots.to_string());
+                                ui.label(format!("{:.0}", scoreboard.adr(player)));
+                                ui.end_row();
+                            }
+                        });
+                    ui.add_space(8.0);
+                }
+
+                if scoreboard.players.is_empty() {
+                    ui.label("No match events yet");
+                }
+            });
+
+        self.scoreboard_window_open = open;
+    }
+
+    fn refresh_log_files(&mut self) {
+        self.log_files = match session_log::log_dir() {
+            Some(dir) => session_log::list(&dir),
+            None => Vec::new(),
+        };
+    }
+
+    fn show_logs_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.logs_window_open;
+        let mut refresh = false;
+        let mut selected: Option<PathBuf> = None;
+        let mut export = false;
+
+        if self.log_filter.as_ref() != Some(&self.log_search) {
+            self.log_lines = session_log::matching_lines(&self.log_content, &self.log_search);
+            self.log_filter = Some(self.log_search.clone());
+        }
+
+        egui::Window::new("Server logs")
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Max file size").on_hover_text(
+                        "Session continues in next file when current one gets bigger",
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut self.log_rotation.max_file_mb)
+                            .clamp_range(1..=1024)
+                            .suffix(" MB"),
+                    );
+                    ui.label("Keep for").on_hover_text(
+                        "Older logs are removed when new session starts, 0 keeps all",
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut self.log_rotation.max_age_days)
+                            .clamp_range(0..=3650)
+                            .suffix(" days"),
+                    );
+                    if ui
+                        .button("Refresh")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        refresh = true;
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .id_source("log_files")
+                    .max_height(140.0)
+                    .show(ui, |ui| {
+                        if self.log_files.is_empty() {
+                            ui.label("No logs yet");
+                        }
+                        for file in &self.log_files {
+                            let is_selected = self.selected_log.as_ref() == Some(&file.path);
+                            let label = format!(
+                                "{}  ({} KB, {} UTC)",
+                                file.name,
+                                (file.size + 1023) / 1024,
+                                utils::format_utc(file.modified)
+                            );
+                            if ui.selectable_label(is_selected, label).clicked() {
+                                selected = Some(file.path.clone());
+                            }
+                        }
+                    });
+                ui.separator();
+
+                ui.add_enabled_ui(self.selected_log.is_some(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Search");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.log_search).desired_width(160.0),
+                        );
+                        ui.label("Export to");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.log_export_path)
+                                .desired_width(200.0),
+                        );
+                        if ui
+                            .add_enabled(
+                                !self.log_export_path.is_empty(),
+                                egui::Button::new("Export"),
+                            )
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            export = true;
+                        }
+                    });
+                });
+                if !self.log_status.is_empty() {
+                    ui.label(&self.log_status);
+                }
+
+                // only visible rows are laid out, match logs are tens of thousands of lines
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                egui::ScrollArea::vertical()
+                    .id_source("log_content")
+                    .max_height(280.0)
+                    .show_rows(ui, row_height, self.log_lines.len(), |ui, rows| {
+                        for range in &self.log_lines[rows] {
+                            ui.monospace(&self.log_content[range.clone()]);
+                        }
+                    });
+            });
+
+        if refresh {
+            self.refresh_log_files();
+        }
+        if let Some(path) = selected {
+            self.log_content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) => format!("Unable to read {}: {err}", path.display()),
+            };
+            self.log_status.clear();
+            self.log_filter = None;
+            self.selected_log = Some(path);
+        }
+        if export {
+            if let Some(path) = &self.selected_log {
+                self.log_status = match session_log::export(path, Path::new(&self.log_export_path))
+                {
+                    Ok(_) => format!("Exported to {}", self.log_export_path),
+                    Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                        format!(
+                            "{} already exists, choose another file",
+                            self.log_export_path
+                        )
+                    }
+                    Err(err) => format!("Unable to export: {err}"),
+                };
+            }
+        }
+
+        self.logs_window_open = open;
+    }
+
+    fn show_watchdog_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.watchdog_window_open;
+
+        egui::Window::new("Watchdog")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("watchdog")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Restart on crash")
+                            .on_hover_text("Start server again with the same arguments if it exits abnormally");
+                        ui.checkbox(&mut self.watchdog_settings.enabled, "").on_hover_cursor(CursorIcon::PointingHand);
+                        ui.end_row();
+
+                        ui.label("Restart delay")
+                            .on_hover_text("Delay before first restart, doubled after every following crash");
+                        ui.add(egui::DragValue::new(&mut self.watchdog_settings.restart_delay_secs).clamp_range(1..=600).suffix(" s"));
+                        ui.end_row();
+
+                        ui.label("Max attempts")
+                            .on_hover_text("Restarts in a row before giving up, counter is reset after 5 minutes of stable uptime");
+                        ui.add(egui::DragValue::new(&mut self.watchdog_settings.max_attempts).clamp_range(1..=100));
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                ui.label("Restart history");
+                if self.watchdog.history.is_empty() {
+                    ui.label("No crashes so far");
+                }
+                egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for record in self.watchdog.history.iter().rev() {
+                        let outcome = match record.attempt {
+                            Some(attempt) => format!("restart attempt {attempt}"),
+                            None => String::from("not restarted"),
+                        };
+                        ui.label(format!(
+                            "{}s ago: {} after {}s uptime, {}",
+                            record.crashed_at.elapsed().as_secs(),
+                            record.exit,
+                            record.uptime.as_secs(),
+                            outcome
+                        ));
+                    }
+                });
+            });
+
+        self.watchdog_window_open = open;
+    }
+
+    fn show_launch_warnings(&mut self, ctx: &egui::Context) {
+        if self.launch_warnings.is_empty() {
+            return;
+        }
+
+        let mut launch_anyway = false;
+        let mut cancel = false;
+
+        egui::Window::new("Game may be out of date")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for warning in &self.launch_warnings {
+                    ui.colored_label(Color32::YELLOW, warning);
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Launch anyway")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        launch_anyway = true;
+                    }
+                    if ui
+                        .button("Cancel")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if launch_anyway || cancel {
+            self.launch_warnings.clear();
+        }
+        if launch_anyway {
+            self.launch_server();
+        }
+    }
+
+    fn start_steamcmd(&mut self) {
+        self.steamcmd_output.clear();
+        self.steamcmd_progress = None;
+
+        match steamcmd::start(
+            Path::new(&self.steamcmd_path),
+            Path::new(&self.steamcmd_install_dir),
+            self.game().app_id(),
+            self.steamcmd_validate,
+        ) {
+            Ok(events) => {
+                self.steamcmd_status = String::from("Running steamcmd");
+                self.steamcmd_events = Some(events);
+            }
+            Err(err) => self.steamcmd_status = format!("Unable to start steamcmd: {err}"),
+        }
+    }
+
+    fn poll_steamcmd(&mut self, ctx: &egui::Context) {
+        let Some(events) = &self.steamcmd_events else {
+            return;
+        };
+
+        let mut finished = false;
+        loop {
+            match events.try_recv() {
+                Ok(steamcmd::SteamCmdEvent::Output(line)) => {
+                    self.steamcmd_output.push(line);
+                    if self.steamcmd_output.len() > STEAMCMD_OUTPUT_LINES {
+                        self.steamcmd_output.remove(0);
+                    }
+                }
+                Ok(steamcmd::SteamCmdEvent::Progress(progress)) => {
+                    self.steamcmd_progress = Some(progress)
+                }
+                Ok(steamcmd::SteamCmdEvent::Finished(result)) => {
+                    self.steamcmd_status = match result {
+                        Ok(_) => String::from("Server is installed and up to date"),
+                        Err(err) => err,
+                    };
+                    finished = true;
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        if finished {
+            self.steamcmd_events = None;
+        } else {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
 
-    // 1/0
-    mp_friendlyfire: bool,
+    fn show_steamcmd_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.steamcmd_window_open;
+        let mut use_install = false;
+        let running = self.steamcmd_events.is_some();
 
-    // int
-    mp_maxrounds: u32,
+        egui::Window::new("SteamCMD server install")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("steamcmd")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("steamcmd executable")
+                            .on_hover_text("Path to steamcmd.exe, download it from https://developer.valvesoftware.com/wiki/SteamCMD");
+                        ui.add(egui::TextEdit::singleline(&mut self.steamcmd_path).desired_width(360.0));
+                        ui.end_row();
 
-    //     0 - no random spawns
-    //     1 - everyone spawns in random locations (like deathmatch)
-    //     2 - only terrorists spawn at random locations, CTs spawn at their spawn
-    //     3 - only CTs spawn at random locations, terrorists spawn at their spawn
-    mp_randomspawn: u8,
+                        ui.label("Install directory")
+                            .on_hover_text("Directory for dedicated server copy, passed as +force_install_dir");
+                        ui.add(egui::TextEdit::singleline(&mut self.steamcmd_install_dir).desired_width(360.0));
+                        ui.end_row();
 
-    // seconds
-    mp_roundtime: u32,
+                        ui.label("validate")
+                            .on_hover_text("Verify all installed files, slower but fixes broken installs");
+                        ui.checkbox(&mut self.steamcmd_validate, "").on_hover_cursor(CursorIcon::PointingHand);
+                        ui.end_row();
+                    });
 
-    // seconds
-    mp_warmuptime: u32,
+                ui.horizontal(|ui| {
+                    let can_start = !running
+                        && !self.steamcmd_path.is_empty()
+                        && !self.steamcmd_install_dir.is_empty();
+                    if ui.add_enabled(can_start, egui::Button::new("Install / Update"))
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked() {
+                        self.start_steamcmd();
+                    }
+                    if ui.add_enabled(!running && !self.steamcmd_install_dir.is_empty(), egui::Button::new("Use as game path"))
+                        .on_hover_text("Run servers from this install instead of Steam client copy")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked() {
+                        use_install = true;
+                    }
+                });
 
-    mp_endwarmup_player_count: u32,
+                if let Some(progress) = &self.steamcmd_progress {
+                    ui.add(
+                        egui::ProgressBar::new(progress.percent / 100.0)
+                            .text(format!("{} {:.2}%", progress.state, progress.percent))
+                    );
+                }
+                ui.label(&self.steamcmd_status);
 
-    // number, tickrate
-    sv_minupdaterate: u32,
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in &self.steamcmd_output {
+                            ui.monospace(line);
+                        }
+                    });
+            });
 
-    // "0" to disable or password
-    sv_password: String,
+        self.steamcmd_window_open = open;
+        if use_install {
+            installations::register(
+                &mut self.installations,
+                STEAMCMD_INSTALLATION_LABEL,
+                &self.steamcmd_install_dir,
+            );
+            self.installation_details
+                .remove(STEAMCMD_INSTALLATION_LABEL);
+            self.settings.installation = String::from(STEAMCMD_INSTALLATION_LABEL);
+            self.start_scan();
+        }
+    }
 
-    game_alias: String,
+    fn show_installations_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.installations_window_open;
+        let mut selected: Option<String> = None;
+        let mut removed: Option<String> = None;
+        let mut refresh = false;
 
-    map_name: String,
-    game_path_str: String,
+        egui::Window::new("Installations")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("installations")
+                            .num_columns(5)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for installation in &self.installations {
+                                    ui.label(&installation.label)
+                                        .on_hover_text(&installation.path);
+                                    match self.installation_details.get(&installation.label) {
+                                        Some(details) => {
+                                            let version = details
+                                                .version
+                                                .as_ref()
+                                                .map_or(String::from("unknown version"), |v| {
+                                                    v.patch_version.clone()
+                                                });
+                                            ui.label(version);
+                                            ui.label(format!("{} maps", details.maps.len()))
+                                                .on_hover_text(details.maps.join("\n"));
+                                            if details.is_complete() {
+                                                ui.colored_label(Color32::GREEN, "✔");
+                                            } else {
+                                                ui.colored_label(Color32::RED, "✘").on_hover_ui(
+                                                    |ui| install_checklist(ui, &details.checks),
+                                                );
+                                            }
+                                        }
+                                        None => {
+                                            ui.label("-");
+                                            ui.label("-");
+                                            ui.label("-");
+                                        }
+                                    }
+                                    ui.horizontal(|ui| {
+                                        let active =
+                                            self.settings.installation == installation.label;
+                                        if ui
+                                            .add_enabled(!active, egui::Button::new("Use"))
+                                            .on_hover_text(
+                                                "Run current profile from this installation",
+                                            )
+                                            .on_hover_cursor(CursorIcon::PointingHand)
+                                            .clicked()
+                                        {
+                                            selected = Some(installation.label.clone());
+                                        }
+                                        if ui
+                                            .button("Remove")
+                                            .on_hover_cursor(CursorIcon::PointingHand)
+                                            .clicked()
+                                        {
+                                            removed = Some(installation.label.clone());
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
 
-    #[serde(skip)]
-    game_path: String,
+                if ui
+                    .button("Refresh")
+                    .on_hover_text("Read version and maps of every installation")
+                    .clicked()
+                {
+                    refresh = true;
+                }
 
-    #[serde(skip)]
-    error_title: String,
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_installation_label)
+                            .hint_text("Label")
+                            .desired_width(120.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_installation_path)
+                            .hint_text("Game directory")
+                            .desired_width(300.0),
+                    );
+                    let can_add = !self.new_installation_label.is_empty()
+                        && !self.new_installation_path.is_empty();
+                    if ui
+                        .add_enabled(can_add, egui::Button::new("Add"))
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        installations::register(
+                            &mut self.installations,
+                            &self.new_installation_label,
+                            &self.new_installation_path,
+                        );
+                        self.installation_details
+                            .remove(&self.new_installation_label);
+                        self.new_installation_label.clear();
+                        self.new_installation_path.clear();
+                        refresh = true;
+                    }
+                });
+            });
 
-    #[serde(skip)]
-    error_msg: String,
+        self.installations_window_open = open;
 
-    #[serde(skip)]
-    ready: bool,
+        if let Some(label) = removed {
+            self.installations.retain(|i| i.label != label);
+            self.installation_details.remove(&label);
+            if self.settings.installation == label {
+                self.settings.installation.clear();
+                self.start_scan();
+            }
+        }
+        if let Some(label) = selected {
+            self.settings.installation = label;
+            self.start_scan();
+        }
+        if refresh || (open && self.installation_details.len() != self.installations.len()) {
+            let game = self.game();
+            for installation in &self.installations {
+                if refresh || !self.installation_details.contains_key(&installation.label) {
+                    self.installation_details.insert(
+                        installation.label.clone(),
+                        installations::inspect(game, installation),
+                    );
+                }
+            }
+        }
+    }
 
-    #[serde(skip)]
-    is_server_running: bool,
+    fn show_catalog_window(&mut self, ctx: &egui::Context) {
+        if !self.catalog_window_open {
+            return;
+        }
+        let mut open = self.catalog_window_open;
+        let mut compare = false;
 
-    #[serde(skip)]
-    available_maps: Vec<String>,
+        egui::Window::new("Convar catalog")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label("Save output of `cvarlist` or `find` from server console to a text file and compare it with bundled catalog");
+                ui.horizontal(|ui| {
+                    let path_label = ui.label("File: ");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.convar_dump_path)
+                            .desired_width(360.0)
+                    ).labelled_by(path_label.id);
 
-    #[serde(skip)]
-    error_popup_open: bool,
+                    if ui.button("Compare")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked() {
+                        compare = true;
+                    };
+                });
+                ui.label(&self.catalog_status);
 
-    #[serde(skip)]
-    available_steam_apps: LinkedHashMap<u32, steamlocate::App>,
-}
+                ui.horizontal(|ui| {
+                    let path_label = ui.label("Save to: ");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.catalog_save_path)
+                            .desired_width(360.0)
+                    ).labelled_by(path_label.id);
+                    let can_save = !self.catalog_entries.is_empty() && !self.catalog_save_path.is_empty();
+                    if ui.add_enabled(can_save, egui::Button::new("Save as catalog"))
+                        .on_hover_text("Write parsed entries in bundled catalog format, existing file is not replaced")
+                        .clicked() {
+                        let target = PathBuf::from(&self.catalog_save_path);
+                        self.catalog_status = match convars::save_catalog(&target, &self.catalog_entries) {
+                            Ok(_) => format!("Catalog saved to {}", target.display()),
+                            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                                format!("{} already exists, choose another file", target.display())
+                            }
+                            Err(err) => format!("Unable to save catalog: {err}"),
+                        };
+                    };
+                });
 
-impl Default for CS2ServerPrestarterApp {
-    fn default() -> Self {
-        let mut game_path: String = String::new();
-        let mut available_maps: Vec<String> = Vec::new();
-        let mut ready = false;
-        let mut available_apps: LinkedHashMap<u32, steamlocate::App> = LinkedHashMap::new();
-
-        let (state, error, error_title) = match steam::get_steam_dir_for_app(CS2APPID) {
-            Ok(res) => {
-                game_path = res;
-                (steam::ReadyState::Ready, "".to_string(), "".to_string())
-            }
-            Err(err) => match err {
-                steamlocate::Error::FailedLocate(_) | steamlocate::Error::InvalidSteamDir(_) => (
-                    steam::ReadyState::NoSteam,
-                    "Unable to find SteamLibrary directory. Are you sure you have Steam installed?"
-                        .to_string(),
-                    "Steam not found".to_string(),
-                ),
-                steamlocate::Error::MissingExpectedApp { app_id } => (
-                    steam::ReadyState::NoGame,
-                    format!("Unable to find app with id {app_id}. \nAre you sure it is installed?"),
-                    "Game not found".to_string(),
-                ),
-                _ => (
-                    steam::ReadyState::NoSteam,
-                    format!("Unknown error occurred: {err}"),
-                    "Unknown error".to_string(),
-                ),
-            },
-        };
+                if let Some(diff) = &self.catalog_diff {
+                    if diff.is_empty() {
+                        ui.label("Bundled catalog is up to date");
+                    }
+                    egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                        egui::CollapsingHeader::new(format!("Changed ({})", diff.changed.len()))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for (old, new) in &diff.changed {
+                                    ui.label(format!("{}: \"{}\" -> \"{}\"", new.name, old.default, new.default))
+                                        .on_hover_text(&new.help);
+                                }
+                            });
+                        egui::CollapsingHeader::new(format!("Removed ({})", diff.removed.len()))
+                            .show(ui, |ui| {
+                                for old in &diff.removed {
+                                    ui.label(&old.name);
+                                }
+                            });
+                        egui::CollapsingHeader::new(format!("Added ({})", diff.added.len()))
+                            .show(ui, |ui| {
+                                for new in &diff.added {
+                                    ui.label(format!("{} = \"{}\"", new.name, new.default))
+                                        .on_hover_text(&new.help);
+                                }
+                            });
+                    });
+                }
+            });
 
-        match state {
-            steam::ReadyState::Ready => {
-                available_maps = match counter_strike::get_available_maps(&game_path) {
-                    None => Vec::new(),
-                    Some(res) => res,
-                };
-                ready = true;
-            }
-            steam::ReadyState::NoGame => {
-                available_apps = steam::get_available_apps().unwrap();
+        if compare {
+            match convars::load_catalog(Path::new(&self.convar_dump_path)) {
+                Ok(entries) => {
+                    let catalog = self.game().convar_catalog();
+                    let bundled = self
+                        .bundled_catalog
+                        .get_or_insert_with(|| convars::parse_cvarlist(catalog));
+                    self.catalog_status = format!("Parsed {} entries", entries.len());
+                    self.catalog_diff = Some(convars::diff_catalogs(bundled, &entries));
+                    self.catalog_entries = entries;
+                    if self.catalog_save_path.is_empty() {
+                        self.catalog_save_path = Path::new(&self.convar_dump_path)
+                            .with_file_name("convars.txt")
+                            .to_string_lossy()
+                            .into_owned();
+                    }
+                }
+                Err(err) => {
+                    self.catalog_status = format!("Unable to read file: {err}");
+                    self.catalog_diff = None;
+                    self.catalog_entries.clear();
+                }
             }
-            steam::ReadyState::NoSteam => {}
-        };
-
-        Self {
-            game_path,
-            ready,
-            app_id: 730,
-            game_path_str: "".to_string(),
-            map_name: "de_dust2".to_string(),
-
-            insecure: true,
-            mp_autokick: true,
-            mp_buy_anywhere: 0u8,
-            mp_buytime: 15,
-            mp_c4timer: 40,
-            mp_freezetime: 20,
-            mp_friendlyfire: true,
-            mp_maxrounds: 32,
-            mp_endwarmup_player_count: 2,
-            mp_randomspawn: 0,
-            mp_roundtime: 115,
-            mp_warmuptime: 15,
-            sv_minupdaterate: 64,
-            sv_password: "0".to_string(),
-
-            game_alias: "competitive".to_string(),
-
-            error_msg: error,
-            error_title,
-            available_maps,
-            is_server_running: false,
-
-            error_popup_open: true,
-            available_steam_apps: available_apps,
-        }
-    }
-}
-
-impl CS2ServerPrestarterApp {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        let style = Style {
-            visuals: Visuals::dark(),
-            ..Style::default()
-        };
-        cc.egui_ctx.set_style(style);
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
         }
-        Default::default()
+        self.catalog_window_open = open;
     }
 }
 
@@ -188,11 +2495,122 @@ impl eframe::App for CS2ServerPrestarterApp {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 egui::widgets::global_dark_light_mode_buttons(ui);
+                if ui
+                    .add_enabled(self.scan_events.is_none(), egui::Button::new("Rescan"))
+                    .on_hover_text("Search Steam libraries for game and maps again")
+                    .clicked()
+                {
+                    self.start_scan();
+                }
+                ui.menu_button("Tools", |ui| {
+                    if ui.button("Export/import settings").clicked() {
+                        self.settings_file_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Convar catalog").clicked() {
+                        self.catalog_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("SteamCMD server install").clicked() {
+                        self.steamcmd_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Installations").clicked() {
+                        self.installations_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Server console").clicked() {
+                        self.console_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Scoreboard").clicked() {
+                        self.scoreboard_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Demos").clicked() {
+                        self.demos_window_open = true;
+                        self.refresh_demos();
+                        ui.close_menu();
+                    }
+                    if ui.button("Players").clicked() {
+                        self.players_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Match control").clicked() {
+                        self.match_control_window_open = true;
+                        if self.control_map.is_empty() {
+                            self.control_map = self.settings.map_name.clone();
+                        }
+                        ui.close_menu();
+                    }
+                    if ui.button("Round backups").clicked() {
+                        self.backups_window_open = true;
+                        self.refresh_round_backups();
+                        ui.close_menu();
+                    }
+                    if ui.button("Past matches").clicked() {
+                        self.matches_window_open = true;
+                        self.refresh_past_matches();
+                        ui.close_menu();
+                    }
+                    if ui.button("Leaderboard").clicked() {
+                        self.leaderboard_window_open = true;
+                        self.refresh_leaderboard();
+                        ui.close_menu();
+                    }
+                    if ui.button("Log receiver").clicked() {
+                        self.log_receiver_window_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Server logs").clicked() {
+                        self.logs_window_open = true;
+                        self.refresh_log_files();
+                        ui.close_menu();
+                    }
+                    if ui.button("Watchdog").clicked() {
+                        self.watchdog_window_open = true;
+                        ui.close_menu();
+                    }
+                });
             });
         });
 
+        self.poll_scan(ctx);
+        self.show_catalog_window(ctx);
+        self.show_settings_file_window(ctx);
+        self.poll_steamcmd(ctx);
+        self.show_steamcmd_window(ctx);
+        self.show_installations_window(ctx);
+        self.poll_server(ctx);
+        self.show_console_window(ctx);
+        self.poll_log_receiver(ctx);
+        self.save_finished_match();
+        self.show_demos_window(ctx);
+        self.poll_players();
+        self.show_players_window(ctx);
+        self.show_match_control_window(ctx);
+        self.show_backups_window(ctx);
+        self.show_matches_window(ctx);
+        self.show_leaderboard_window(ctx);
+        self.show_log_receiver_window(ctx);
+        self.show_scoreboard_window(ctx);
+        self.show_logs_window(ctx);
+        self.show_watchdog_window(ctx);
+
+        if self.scan_events.is_some() {
+            egui::TopBottomPanel::bottom("scan_progress").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(&self.scan_progress);
+                });
+            });
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            if !self.ready {
+            if !self.ready && self.scan_events.is_none() {
+                let mut selected_app: Option<u32> = None;
+                let mut selected_dir: Option<String> = None;
+
                 egui::CentralPanel::default().show(ctx, |ui| {
                     egui::Window::new(&self.error_title)
                         .vscroll(true)
@@ -201,18 +2619,40 @@ impl eframe::App for CS2ServerPrestarterApp {
                         .movable(false)
                         .show(ctx, |ui| {
                             egui::ScrollArea::vertical().max_height(480.0).auto_shrink(true).show(ui, |ui| {
-                                ui.add(
-                                    egui::TextEdit::multiline(&mut self.error_msg.as_str())
-                                        .desired_width(640f32)
-                                );
+                                for problem in &self.discovery_problems {
+                                    ui.colored_label(Color32::RED, problem.to_string());
+                                }
+                                install_checklist(ui, &self.install_checks);
+                                if !self.game_path_str.is_empty()
+                                    && ui.button("Use Steam detection")
+                                        .on_hover_text("Forget custom game folder")
+                                        .on_hover_cursor(CursorIcon::PointingHand)
+                                        .clicked() {
+                                    selected_app = Some(self.app_id);
+                                }
+                                ui.separator();
+                                ui.horizontal(|ui| {
+                                    let dir_label = ui.label("Game folder: ");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut self.manual_game_dir)
+                                            .hint_text("C:\\Program Files (x86)\\Steam\\steamapps\\common\\Counter-Strike Global Offensive")
+                                            .desired_width(400.0)
+                                    ).labelled_by(dir_label.id);
+                                    if ui.add_enabled(!self.manual_game_dir.is_empty(), egui::Button::new("Use folder"))
+                                        .on_hover_cursor(CursorIcon::PointingHand)
+                                        .clicked() {
+                                        selected_dir = Some(self.manual_game_dir.clone());
+                                    }
+                                });
+                                ui.separator();
                                 egui::Grid::new("")
-                                    .num_columns(2)
+                                    .num_columns(3)
                                     .show(ui, |ui| {
                                         for k in self.available_steam_apps.keys() {
                                             ui.label(k.to_string());
-                                            ui.label(self.available_steam_apps[k].name.clone().unwrap());
+                                            ui.label(self.available_steam_apps[k].name.clone().unwrap_or_default());
                                             if ui.button("Select").on_hover_cursor(CursorIcon::PointingHand).clicked() {
-                                                self.app_id = *k;
+                                                selected_app = Some(*k);
                                             }
                                             ui.end_row();
                                         }
@@ -220,18 +2660,65 @@ impl eframe::App for CS2ServerPrestarterApp {
                             });
                         });
                 });
+
+                if let Some(app_id) = selected_app {
+                    self.app_id = app_id;
+                    self.game_path_str.clear();
+                    self.start_scan();
+                } else if let Some(dir) = selected_dir {
+                    self.game_path_str = dir;
+                    self.start_scan();
+                }
             }
 
 
-            ui.heading("CS2 Server Settings");
+            ui.heading(format!("{} Server Settings", self.game().name()));
+
+            let report = validation::validate(&self.settings);
+
+            let mut apply_game_path = false;
+            let mut launch_requested = false;
+            let mut stop_requested = false;
 
             ui.add_enabled_ui(self.ready, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Installation: ");
+                    let previous = self.settings.installation.clone();
+                    egui::ComboBox::from_id_source("installation")
+                        .selected_text(if self.settings.installation.is_empty() {
+                            "Steam / custom path"
+                        } else {
+                            self.settings.installation.as_str()
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.settings.installation, String::new(), "Steam / custom path");
+                            for installation in &self.installations {
+                                ui.selectable_value(&mut self.settings.installation, installation.label.clone(), &installation.label)
+                                    .on_hover_text(&installation.path);
+                            }
+                        });
+                    if previous != self.settings.installation {
+                        apply_game_path = true;
+                    }
+                });
+
                 ui.horizontal(|ui| {
                     let name_label = ui.label("Game path: ");
-                    ui.add(
-                        egui::TextEdit::singleline(&mut self.game_path)
-                            .desired_width(400.0)
-                    ).labelled_by(name_label.id);
+                    ui.add_enabled(
+                        self.settings.installation.is_empty(),
+                        egui::TextEdit::singleline(&mut self.game_path_str)
+                            .hint_text(&self.game_path)
+                            .desired_width(320.0)
+                    )
+                        .labelled_by(name_label.id)
+                        .on_hover_text("Custom install directory, takes precedence over Steam detection. Leave empty to use Steam");
+
+                    if ui.button("Apply")
+                        .on_hover_text("Check install directory and reload maps")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked() {
+                        apply_game_path = true;
+                    };
 
                     if ui.button("Open")
                         .on_hover_text("Open game directory")
@@ -245,271 +2732,495 @@ impl eframe::App for CS2ServerPrestarterApp {
                     };
                 });
 
-                egui::Grid::new("")
-                    .num_columns(2)
-                    .show(ui, |ui| {
-                        ui.label("insecure")
-                            .on_hover_text("Disable VAC on your server. If enabled you have to add `-insecure` to your CS2 start params")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.checkbox(&mut self.insecure, "").on_hover_cursor(CursorIcon::PointingHand);
-                        ui.end_row();
+                ui.horizontal(|ui| {
+                    if let Some(version) = &self.game_version {
+                        ui.label(format!("Version {}", version.patch_version))
+                            .on_hover_text(format!(
+                                "Client version: {}\nServer version: {}",
+                                version.client_version, version.server_version
+                            ));
+                    }
+                    if let Some(manifest) = &self.app_manifest {
+                        ui.label(format!("build {}", manifest.build_id));
+                        for problem in manifest.problems() {
+                            ui.colored_label(Color32::YELLOW, "⚠").on_hover_text(problem);
+                        }
+                    }
+                });
 
-                        ui.label("mp_autokick")
-                            .on_hover_text("Kick for AFK or team dmg")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.checkbox(&mut self.mp_autokick, "").on_hover_cursor(CursorIcon::PointingHand);
-                        ui.end_row();
+                ui.collapsing("Install check", |ui| {
+                    install_checklist(ui, &self.install_checks);
+                });
 
-                        ui.label("mp_buy_anywhere")
-                            .on_hover_text("Can anyone buy anywhere on map.")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.horizontal(|ui| {
-                            ui.radio_value(&mut self.mp_buy_anywhere, 0, "Only buy zone").on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.mp_buy_anywhere, 1, "All teams").on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.mp_buy_anywhere, 2, "T only").on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.mp_buy_anywhere, 3, "CT only").on_hover_cursor(CursorIcon::PointingHand);
-                        });
-                        ui.end_row();
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("")
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            ui.label("hostname")
+                                .on_hover_text("Server name shown in server browser")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.text_edit_singleline(&mut self.settings.hostname);
+                            field_issues(ui, &report, "hostname");
+                            ui.end_row();
 
-                        ui.label("mp_buytime")
-                            .on_hover_text("Time to buy after freezetime in seconds")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_buytime)
-                                .speed(0.1)
-                                .clamp_range(0..=self.mp_roundtime)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("sv_tags")
+                                .on_hover_text("Comma separated tags to find server in browser")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.text_edit_singleline(&mut self.settings.sv_tags);
+                            field_issues(ui, &report, "sv_tags");
+                            ui.end_row();
 
-                        ui.label("mp_c4timer")
-                            .on_hover_text("Seconds before explosion after planted")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_c4timer)
-                                .speed(0.1)
-                                .clamp_range(0..=self.mp_roundtime)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("sv_region")
+                                .on_hover_text("Region reported to master server")
+                                .on_hover_cursor(CursorIcon::Default);
+                            egui::ComboBox::from_id_source("sv_region")
+                                .selected_text(region_name(self.settings.sv_region))
+                                .show_ui(ui, |ui| {
+                                    for region in [255u8, 0, 1, 2, 3, 4, 5, 6, 7] {
+                                        ui.selectable_value(&mut self.settings.sv_region, region, region_name(region)).on_hover_cursor(CursorIcon::PointingHand);
+                                    }
+                                });
+                            ui.end_row();
 
-                        ui.label("mp_freezetime")
-                            .on_hover_text("Freeze time before round start in seconds")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_freezetime)
-                                .speed(0.1)
-                                .clamp_range(0..=self.mp_roundtime)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("sv_lan")
+                                .on_hover_text("Allow only players from local network")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.checkbox(&mut self.settings.sv_lan, "").on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
 
-                        ui.label("mp_friendlyfire")
-                            .on_hover_text("Enable friendly fire or not")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.checkbox(&mut self.mp_friendlyfire, "").on_hover_cursor(CursorIcon::PointingHand);
-                        ui.end_row();
+                            ui.label("maxplayers")
+                                .on_hover_text("Player slots on server")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.maxplayers)
+                                    .speed(0.1)
+                                    .clamp_range(1..=validation::MAX_PLAYERS_LIMIT)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            field_issues(ui, &report, "maxplayers");
+                            ui.end_row();
 
-                        ui.label("mp_maxrounds")
-                            .on_hover_text("Max rounds (for both teams summary). Team switch at half of this value")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_maxrounds)
-                                .speed(0.1)
-                                .clamp_range(0..=4096)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("sv_visiblemaxplayers")
+                                .on_hover_text("Slots count shown in server browser, -1 to show actual value")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.sv_visiblemaxplayers)
+                                    .speed(0.1)
+                                    .clamp_range(-1..=validation::MAX_PLAYERS_LIMIT as i32)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            field_issues(ui, &report, "sv_visiblemaxplayers");
+                            ui.end_row();
 
-                        ui.label("mp_randomspawn")
-                            .on_hover_text("Enable random spawn")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.horizontal(|ui| {
-                            ui.radio_value(&mut self.mp_randomspawn, 0, "Disabled")
-                                .on_hover_text("no random spawns")
-                                .on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.mp_randomspawn, 1, "Everyone")
-                                .on_hover_text("everyone spawns in random locations (like deathmatch)")
-                                .on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.mp_randomspawn, 2, "T only")
-                                .on_hover_text("only terrorists spawn at random locations, CTs spawn at their spawn")
-                                .on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.mp_randomspawn, 3, "CT only")
-                                .on_hover_text("only CTs spawn at random locations, terrorists spawn at their spawn")
-                                .on_hover_cursor(CursorIcon::PointingHand);
-                        });
-                        ui.end_row();
+                            ui.label("motd")
+                                .on_hover_text("Message of the day, saved to game/csgo/motd.txt on start. Leave empty to keep existing file")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add(
+                                egui::TextEdit::multiline(&mut self.settings.motd)
+                                    .desired_rows(3)
+                            );
+                            ui.end_row();
 
-                        ui.label("mp_roundtime")
-                            .on_hover_text("Round time in seconds")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_roundtime)
-                                .speed(0.1)
-                                .clamp_range(0..=3600)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("insecure")
+                                .on_hover_text("Disable VAC on your server. If enabled you have to add `-insecure` to your CS2 start params")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.checkbox(&mut self.settings.insecure, "").on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
 
-                        ui.label("mp_warmuptime")
-                            .on_hover_text("Warmup duration in seconds")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_warmuptime)
-                                .speed(0.1)
-                                .clamp_range(0..=3600)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
-                        ui.label("mp_endwarmup_player_count")
-                            .on_hover_text("Players to connect to skip warmup")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.mp_endwarmup_player_count)
-                                .speed(0.1)
-                                .clamp_range(0..=3600)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("sv_setsteamaccount")
+                                .on_hover_text("Game Server Login Token for VAC-secured public server. Create one at https://steamcommunity.com/dev/managegameservers")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.settings.sv_setsteamaccount)
+                                        .password(!self.show_login_token)
+                                        .desired_width(240.0)
+                                );
+                                ui.checkbox(&mut self.show_login_token, "Show").on_hover_cursor(CursorIcon::PointingHand);
+                            });
+                            field_issues(ui, &report, "sv_setsteamaccount");
+                            ui.end_row();
 
-                        ui.label("sv_minupdaterate")
-                            .on_hover_text("Minimal update rate for server")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.add(
-                            egui::DragValue::new(&mut self.sv_minupdaterate)
-                                .speed(0.1)
-                                .clamp_range(0..=4096)
-                        ).on_hover_cursor(CursorIcon::VerticalText);
-                        ui.end_row();
+                            ui.label("mp_autokick")
+                                .on_hover_text("Kick for AFK or team dmg")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.checkbox(&mut self.settings.mp_autokick, "").on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
 
-                        ui.label("sv_password")
-                            .on_hover_text("Password to join server, type \"0\" for no password")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.text_edit_singleline(&mut self.sv_password);
-                        ui.end_row();
+                            ui.label("mp_buy_anywhere")
+                                .on_hover_text("Can anyone buy anywhere on map.")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut self.settings.mp_buy_anywhere, 0, "Only buy zone").on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.mp_buy_anywhere, 1, "All teams").on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.mp_buy_anywhere, 2, "T only").on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.mp_buy_anywhere, 3, "CT only").on_hover_cursor(CursorIcon::PointingHand);
+                            });
+                            ui.end_row();
 
-                        ui.label("map")
-                            .on_hover_text("Map to play on")
-                            .on_hover_cursor(CursorIcon::Default);
-                        egui::ComboBox::from_label("")
-                            .selected_text(format!("{:?}", self.map_name))
-                            .show_ui(ui, |ui| {
-                                for one_map in &self.available_maps {
-                                    ui.selectable_value(&mut self.map_name, one_map.to_string(), one_map).on_hover_cursor(CursorIcon::PointingHand);
-                                }
+                            ui.label("mp_buytime")
+                                .on_hover_text("Time to buy after freezetime, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_buytime, 0..=3600)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("mp_c4timer")
+                                .on_hover_text("Time before explosion after planted, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_c4timer, 10..=90)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("mp_freezetime")
+                                .on_hover_text("Freeze time before round start, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_freezetime, 0..=3600)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("mp_friendlyfire")
+                                .on_hover_text("Enable friendly fire or not")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.checkbox(&mut self.settings.mp_friendlyfire, "").on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
+
+                            ui.label("mp_maxrounds")
+                                .on_hover_text("Max rounds (for both teams summary). Team switch at half of this value")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.mp_maxrounds)
+                                    .speed(0.1)
+                                    .clamp_range(0..=4096)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            field_issues(ui, &report, "mp_maxrounds");
+                            ui.end_row();
+
+                            ui.label("mp_randomspawn")
+                                .on_hover_text("Enable random spawn")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut self.settings.mp_randomspawn, 0, "Disabled")
+                                    .on_hover_text("no random spawns")
+                                    .on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.mp_randomspawn, 1, "Everyone")
+                                    .on_hover_text("everyone spawns in random locations (like deathmatch)")
+                                    .on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.mp_randomspawn, 2, "T only")
+                                    .on_hover_text("only terrorists spawn at random locations, CTs spawn at their spawn")
+                                    .on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.mp_randomspawn, 3, "CT only")
+                                    .on_hover_text("only CTs spawn at random locations, terrorists spawn at their spawn")
+                                    .on_hover_cursor(CursorIcon::PointingHand);
                             });
-                        if ui.button("Open")
-                            .on_hover_text("Open maps directory")
-                            .on_hover_cursor(CursorIcon::PointingHand)
-                            .clicked() {
-                            if cfg!(target_os = "windows") {
-                                let _ = Command::new("explorer")
-                                    .args([counter_strike::get_maps_dir(&self.game_path)])
-                                    .spawn();
+                            ui.end_row();
+
+                            ui.label("mp_roundtime")
+                                .on_hover_text("Round time, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_roundtime, 0..=3600)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("mp_roundtime_defuse")
+                                .on_hover_text("Round time on bomb defusal maps, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_roundtime_defuse, 0..=3600)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("mp_roundtime_hostage")
+                                .on_hover_text("Round time on hostage rescue maps, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_roundtime_hostage, 0..=3600)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("mp_warmuptime")
+                                .on_hover_text("Warmup duration, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            duration_drag(ui, &mut self.settings.mp_warmuptime, 0..=3600)
+                                .on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+                            ui.label("mp_endwarmup_player_count")
+                                .on_hover_text("Players to connect to skip warmup")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.mp_endwarmup_player_count)
+                                    .speed(0.1)
+                                    .clamp_range(0..=3600)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            field_issues(ui, &report, "mp_endwarmup_player_count");
+                            ui.end_row();
+
+                            ui.label("sv_minupdaterate")
+                                .on_hover_text("Minimal update rate for server")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add(
+                                egui::DragValue::new(&mut self.settings.sv_minupdaterate)
+                                    .speed(0.1)
+                                    .clamp_range(0..=4096)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            field_issues(ui, &report, "sv_minupdaterate");
+                            ui.end_row();
+
+                            ui.label("sv_password")
+                                .on_hover_text("Password to join server, type \"0\" for no password")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.text_edit_singleline(&mut self.settings.sv_password);
+                            field_issues(ui, &report, "sv_password");
+                            ui.end_row();
+
+                            ui.label("map")
+                                .on_hover_text("Map to play on")
+                                .on_hover_cursor(CursorIcon::Default);
+                            egui::ComboBox::from_label("")
+                                .selected_text(format!("{:?}", self.settings.map_name))
+                                .show_ui(ui, |ui| {
+                                    for one_map in &self.available_maps {
+                                        ui.selectable_value(&mut self.settings.map_name, one_map.to_string(), one_map).on_hover_cursor(CursorIcon::PointingHand);
+                                    }
+                                });
+                            if ui.button("Open")
+                                .on_hover_text("Open maps directory")
+                                .on_hover_cursor(CursorIcon::PointingHand)
+                                .clicked() {
+                                if cfg!(target_os = "windows") {
+                                    match game::get_maps_dir(self.game(), &self.game_path) {
+                                        Ok(maps_dir) => {
+                                            let _ = Command::new("explorer")
+                                                .args([maps_dir])
+                                                .spawn();
+                                        }
+                                        Err(err) => log::warn!("Unable to open maps directory: {err}"),
+                                    }
+                                };
                             };
-                        };
-                        ui.end_row();
+                            ui.end_row();
 
-                        ui.label("game_alias")
-                            .on_hover_text("Game type")
-                            .on_hover_cursor(CursorIcon::Default);
-                        ui.horizontal(|ui| {
-                            ui.radio_value(&mut self.game_alias, String::from("competitive"), "Competitive").on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.game_alias, String::from("wingman"), "Wingman").on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.game_alias, String::from("casual"), "Casual").on_hover_cursor(CursorIcon::PointingHand);
-                            ui.radio_value(&mut self.game_alias, String::from("custom"), "Custom").on_hover_cursor(CursorIcon::PointingHand);
+                            ui.label("game_alias")
+                                .on_hover_text("Game type")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.horizontal(|ui| {
+                                ui.radio_value(&mut self.settings.game_alias, String::from("competitive"), "Competitive").on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.game_alias, String::from("wingman"), "Wingman").on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.game_alias, String::from("casual"), "Casual").on_hover_cursor(CursorIcon::PointingHand);
+                                ui.radio_value(&mut self.settings.game_alias, String::from("custom"), "Custom").on_hover_cursor(CursorIcon::PointingHand);
+                            });
+                            ui.end_row();
+
+                            ui.label("mp_teamname_1")
+                                .on_hover_text("Name of team starting as CT, empty for default")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.text_edit_singleline(&mut self.settings.mp_teamname_1);
+                            field_issues(ui, &report, "mp_teamname_1");
+                            ui.end_row();
+
+                            ui.label("mp_teamname_2")
+                                .on_hover_text("Name of team starting as T, empty for default")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.text_edit_singleline(&mut self.settings.mp_teamname_2);
+                            field_issues(ui, &report, "mp_teamname_2");
+                            ui.end_row();
+
+                            ui.strong("GOTV");
+                            ui.end_row();
+
+                            ui.label("tv_enable")
+                                .on_hover_text("Start GOTV relay so spectators can watch and demos can be recorded")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.checkbox(&mut self.settings.tv_enable, "").on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
+
+                            ui.label("tv_delay")
+                                .on_hover_text("Broadcast delay, mm:ss")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add_enabled_ui(self.settings.tv_enable, |ui| {
+                                duration_drag(ui, &mut self.settings.tv_delay, 0..=300)
+                                    .on_hover_cursor(CursorIcon::VerticalText);
+                            });
+                            ui.end_row();
+
+                            ui.label("tv_port")
+                                .on_hover_text("Port spectators connect to")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add_enabled(
+                                self.settings.tv_enable,
+                                egui::DragValue::new(&mut self.settings.tv_port).clamp_range(1024..=65535)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            field_issues(ui, &report, "tv_port");
+                            ui.end_row();
+
+                            ui.label("tv_name")
+                                .on_hover_text("GOTV name shown in scoreboard")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add_enabled(self.settings.tv_enable, egui::TextEdit::singleline(&mut self.settings.tv_name));
+                            field_issues(ui, &report, "tv_name");
+                            ui.end_row();
+
+                            ui.label("tv_maxclients")
+                                .on_hover_text("Max spectators connected to GOTV")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add_enabled(
+                                self.settings.tv_enable,
+                                egui::DragValue::new(&mut self.settings.tv_maxclients).speed(0.1).clamp_range(0..=255)
+                            ).on_hover_cursor(CursorIcon::VerticalText);
+                            ui.end_row();
+
+                            ui.label("tv_autorecord")
+                                .on_hover_text("Let server record every match on its own with default demo names")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add_enabled(self.settings.tv_enable, egui::Checkbox::new(&mut self.settings.tv_autorecord, ""))
+                                .on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
+
+                            ui.label("Record matches")
+                                .on_hover_text("Run tv_record on match start and tv_stoprecord on match end, demo is named by date, map and teams")
+                                .on_hover_cursor(CursorIcon::Default);
+                            ui.add_enabled(
+                                self.settings.tv_enable && !self.settings.tv_autorecord,
+                                egui::Checkbox::new(&mut self.settings.auto_record_demos, "")
+                            ).on_hover_cursor(CursorIcon::PointingHand);
+                            ui.end_row();
                         });
-                        ui.end_row();
-                    });
+                });
 
-                let start_server = ui.button("Run server")
-                    .on_disabled_hover_text("No CS2 found!")
+                let start_server = ui.add_enabled(!report.has_errors() && self.server_process.is_none(), egui::Button::new("Run server"))
+                    .on_disabled_hover_text(if self.ready {
+                        report.errors()
+                            .map(|i| format!("{}: {}", i.field, i.message))
+                            .collect::<Vec<String>>()
+                            .join("\n")
+                    } else {
+                        String::from("No CS2 found!")
+                    })
                     .on_hover_text("Start CS2 server with selected map and settings");
 
-                let mut server_process: Option<Child> = None;
-
                 if start_server.clicked() {
-                    let mut args: Vec<String> = Vec::new();
-                    args.push(String::from("-dedicated"));
-                    args.push(String::from("-insecure"));
-
-                    args.extend_from_slice(&[
-                        String::from("+map"),
-                        String::from(&self.map_name)
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_autokick"),
-                        String::from(&utils::bool_to_str(self.mp_autokick))
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_buy_anywhere"),
-                        String::from(&self.mp_buy_anywhere.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_buytime"),
-                        String::from(&self.mp_buytime.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_c4timer"),
-                        String::from(&self.mp_c4timer.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_freezetime"),
-                        String::from(&self.mp_freezetime.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_friendlyfire"),
-                        String::from(&utils::bool_to_str(self.mp_friendlyfire))
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_maxrounds"),
-                        String::from(&self.mp_maxrounds.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_randomspawn"),
-                        String::from(&self.mp_randomspawn.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_roundtime"),
-                        String::from(&utils::seconds_to_minutes_str(self.mp_roundtime))
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_warmuptime"),
-                        String::from(&self.mp_warmuptime.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+mp_endwarmup_player_count"),
-                        String::from(&self.mp_endwarmup_player_count.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+sv_minupdaterate"),
-                        String::from(&self.sv_minupdaterate.to_string())
-                    ]);
-                    args.extend_from_slice(&[
-                        String::from("+sv_password"),
-                        String::from(&self.sv_password.to_string())
-                    ]);
-
-                    let mut envs: HashMap<String, String> = HashMap::new();
-                    envs.insert(String::from("game_alias"), String::from(&self.game_alias));
-
-                    match create_server_process(&self.game_path, args, envs) {
-                        Ok(res) => {
-                            self.is_server_running = true;
-                            server_process = Some(res);
-                        }
-                        Err(_) => {}
-                    }
+                    launch_requested = true;
                 };
 
-                ui.add_enabled_ui(self.is_server_running, |ui| {
-                    let _ = ui.hyperlink(
-                        format!(
-                            "steam://connect/{url}:{port}/{password}",
-                            url = "127.0.0.1",
-                            port = 27015,
-                            password = self.sv_password
-                        )
-                    );
+                ui.horizontal(|ui| {
+                    let color = match self.readiness.state {
+                        ServerState::Ready => Color32::GREEN,
+                        ServerState::Hibernating => Color32::LIGHT_GREEN,
+                        ServerState::Starting | ServerState::LoadingMap => Color32::YELLOW,
+                        ServerState::Stopped => Color32::GRAY,
+                    };
+                    ui.colored_label(color, format!("\u{25CF} {}", self.readiness.state));
+                    ui.add_enabled_ui(self.readiness.state.is_joinable(), |ui| {
+                        let _ = ui.hyperlink(self.game().connect_url("127.0.0.1", validation::GAME_PORT, &self.settings.sv_password));
+                    });
                 });
+                if let Some(failure) = &self.readiness.failure {
+                    ui.colored_label(Color32::RED, failure);
+                }
+                if let Some(demo) = &self.recording_demo {
+                    ui.colored_label(Color32::LIGHT_RED, format!("\u{25CF} Recording demo {demo}"));
+                }
+
+                if let Some(remaining) = self.watchdog.pending() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(Color32::YELLOW, format!(
+                            "Server crashed, restarting in {}s (attempt {}/{})",
+                            remaining.as_secs(),
+                            self.watchdog.attempts(),
+                            self.watchdog_settings.max_attempts
+                        ));
+                        if ui.button("Cancel").on_hover_cursor(CursorIcon::PointingHand).clicked() {
+                            stop_requested = true;
+                        }
+                    });
+                } else if self.server_process.is_some()
+                    && ui.button("Stop server").on_hover_cursor(CursorIcon::PointingHand).clicked() {
+                    stop_requested = true;
+                }
             });
+
+            if apply_game_path {
+                self.start_scan();
+            }
+            if launch_requested {
+                self.request_launch();
+            }
+            if stop_requested {
+                self.stop_server();
+            }
         });
+
+        self.show_launch_warnings(ctx);
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, eframe::APP_KEY, self);
     }
 }
+
+fn is_iso_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    return matches!(parts[..], [year, month, day]
+        if year.len() == 4 && month.len() == 2 && day.len() == 2
+            && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())));
+}
+
+fn team_color(team: Team) -> Color32 {
+    return match team {
+        Team::CounterTerrorist => Color32::from_rgb(93, 121, 174),
+        Team::Terrorist => Color32::from_rgb(222, 155, 53),
+        Team::Spectator | Team::Unassigned => Color32::GRAY,
+    };
+}
+
+fn field_issues(ui: &mut egui::Ui, report: &validation::Report, field: &str) {
+    ui.vertical(|ui| {
+        for issue in report.for_field(field) {
+            let color = match issue.severity {
+                validation::Severity::Warning => Color32::YELLOW,
+                validation::Severity::Error => Color32::RED,
+            };
+            ui.colored_label(color, &issue.message);
+        }
+    });
+}
+
+// Edits whole seconds while showing and accepting `mm:ss`
+fn duration_drag(
+    ui: &mut egui::Ui,
+    value: &mut ConvarDuration,
+    range: RangeInclusive<u32>,
+) -> egui::Response {
+    let mut seconds = value.as_secs();
+    let response = ui.add(
+        egui::DragValue::new(&mut seconds)
+            .speed(0.1)
+            .clamp_range(range)
+            .custom_formatter(|n, _| ConvarDuration::from_secs(n as u32).to_mm_ss())
+            .custom_parser(|text| ConvarDuration::parse_mm_ss(text).map(|d| d.as_secs() as f64)),
+    );
+    *value = ConvarDuration::from_secs(seconds);
+    return response;
+}
+
+fn region_name(region: u8) -> &'static str {
+    return match region {
+        0 => "US East",
+        1 => "US West",
+        2 => "South America",
+        3 => "Europe",
+        4 => "Asia",
+        5 => "Australia",
+        6 => "Middle East",
+        7 => "Africa",
+        _ => "World",
+    };
+}
+
+fn install_checklist(ui: &mut egui::Ui, checks: &[game::InstallCheck]) {
+    for check in checks {
+        let (mark, color) = if check.found {
+            ("✔", Color32::GREEN)
+        } else {
+            ("✘", Color32::RED)
+        };
+        ui.colored_label(color, format!("{mark} {}", check.name))
+            .on_hover_text(check.path.display().to_string());
+    }
+}
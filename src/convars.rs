@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+// Catalog shipped with the app, kept in the same format the server prints for `cvarlist`
+pub const BUNDLED_CATALOG: &str = include_str!("../assets/convars.txt");
+
+const COLUMN_SEPARATOR: &str = &" :";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConVar {
+    pub name: String,
+    pub default: String,
+    pub flags: Vec<String>,
+    pub help: String,
+}
+
+#[derive(Debug, Default)]
+pub struct CatalogDiff {
+    pub added: Vec<ConVar>,
+    pub removed: Vec<ConVar>,
+    // (bundled, current)
+    pub changed: Vec<(ConVar, ConVar)>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        return self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty();
+    }
+}
+
+// Parses output of `cvarlist` or `find <substring>` as printed by the CS2 dedicated server:
+//     mp_roundtime    : 5    : , "sv", "nf", "rep" : How many minutes each round takes.
+// Header, separator and summary lines are skipped.
+pub fn parse_cvarlist(output: &str) -> Vec<ConVar> {
+    return output.lines().filter_map(parse_line).collect();
+}
+
+fn parse_line(line: &str) -> Option<ConVar> {
+    let line = line.trim_end();
+    let columns: Vec<&str> = line.splitn(4, COLUMN_SEPARATOR).collect();
+    if columns.len() < 3 {
+        return None;
+    }
+
+    let name = columns[0].trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let flags = columns[2]
+        .split(',')
+        .map(|f| f.trim().trim_matches('"'))
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect();
+
+    return Some(ConVar {
+        name: String::from(name),
+        default: String::from(columns[1].trim()),
+        flags,
+        help: String::from(columns.get(3).map_or("", |h| h.trim())),
+    });
+}
+
+pub fn load_catalog(path: &Path) -> io::Result<Vec<ConVar>> {
+    return Ok(parse_cvarlist(&fs::read_to_string(path)?));
+}
+
+// Renders entries back into `cvarlist` format so the result can replace `assets/convars.txt`
+pub fn format_catalog(entries: &[ConVar]) -> String {
+    let mut sorted: Vec<&ConVar> = entries.iter().collect();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = String::from("cvar list\n--------------\n");
+    for entry in &sorted {
+        let flags: Vec<String> = entry.flags.iter().map(|f| format!("\"{f}\"")).collect();
+        let flags = if flags.is_empty() {
+            String::new()
+        } else {
+            format!(", {}", flags.join(", "))
+        };
+        out.push_str(&format!(
+            "{:<40} : {:<8} : {:<16} : {}\n",
+            entry.name, entry.default, flags, entry.help
+        ));
+    }
+    out.push_str(&format!(
+        "--------------\n{} total convars/concommands\n",
+        sorted.len()
+    ));
+    return out;
+}
+
+// Never overwrites existing file, e.g. the dump entries were read from
+pub fn save_catalog(path: &Path, entries: &[ConVar]) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    return file.write_all(format_catalog(entries).as_bytes());
+}
+
+// Only convars known to `bundled` are reported as removed/changed, everything else in `current` is added
+pub fn diff_catalogs(bundled: &[ConVar], current: &[ConVar]) -> CatalogDiff {
+    let current_by_name: HashMap<&str, &ConVar> =
+        current.iter().map(|c| (c.name.as_str(), c)).collect();
+    let bundled_by_name: HashMap<&str, &ConVar> =
+        bundled.iter().map(|c| (c.name.as_str(), c)).collect();
+
+    let mut diff = CatalogDiff::default();
+
+    for old in bundled {
+        match current_by_name.get(old.name.as_str()) {
+            None => diff.removed.push(old.clone()),
+            Some(new) => {
+                if *new != old {
+                    diff.changed.push((old.clone(), (*new).clone()));
+                }
+            }
+        }
+    }
+
+    for new in current {
+        if !bundled_by_name.contains_key(new.name.as_str()) {
+            diff.added.push(new.clone());
+        }
+    }
+
+    diff.added.sort_by(|a, b| a.name.cmp(&b.name));
+    return diff;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(entries: &'a [ConVar], name: &str) -> &'a ConVar {
+        return entries.iter().find(|c| c.name == name).unwrap();
+    }
+
+    fn convar(name: &str, default: &str) -> ConVar {
+        return ConVar {
+            name: String::from(name),
+            default: String::from(default),
+            flags: vec![String::from("sv")],
+            help: String::new(),
+        };
+    }
+
+    #[test]
+    fn parses_bundled_catalog() {
+        let entries = parse_cvarlist(BUNDLED_CATALOG);
+        assert_eq!(entries.len(), 15);
+
+        let roundtime = find(&entries, "mp_roundtime");
+        assert_eq!(roundtime.default, "5");
+        assert_eq!(roundtime.flags, ["sv", "nf", "rep"]);
+        assert_eq!(roundtime.help, "How many minutes each round takes.");
+
+        let alias = find(&entries, "game_alias");
+        assert_eq!(alias.default, "cmd");
+        assert!(alias.flags.is_empty());
+
+        let password = find(&entries, "sv_password");
+        assert_eq!(password.default, "");
+        assert_eq!(password.flags, ["sv", "prot", "nf", "norecord"]);
+    }
+
+    #[test]
+    fn skips_header_and_summary_lines() {
+        let output = "cvar list\n--------------\n\
+            mp_c4timer : 40 : , \"sv\" : How long\n\
+            --------------\n1 total convars/concommands\n";
+        let entries = parse_cvarlist(output);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "mp_c4timer");
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let entries = parse_cvarlist(BUNDLED_CATALOG);
+        let formatted = format_catalog(&entries);
+        assert_eq!(parse_cvarlist(&formatted), entries);
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed() {
+        let bundled = vec![
+            convar("mp_a", "1"),
+            convar("mp_b", "2"),
+            convar("mp_c", "3"),
+        ];
+        let current = vec![
+            convar("mp_d", "4"),
+            convar("mp_b", "20"),
+            convar("mp_c", "3"),
+        ];
+        let diff = diff_catalogs(&bundled, &current);
+
+        assert_eq!(diff.added, [convar("mp_d", "4")]);
+        assert_eq!(diff.removed, [convar("mp_a", "1")]);
+        assert_eq!(diff.changed, [(convar("mp_b", "2"), convar("mp_b", "20"))]);
+        assert!(diff_catalogs(&bundled, &bundled).is_empty());
+    }
+
+    #[test]
+    fn save_does_not_overwrite() {
+        let dir = std::env::temp_dir().join(format!("convars_save_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("convars.txt");
+        let entries = parse_cvarlist(BUNDLED_CATALOG);
+
+        save_catalog(&path, &entries).unwrap();
+        assert_eq!(load_catalog(&path).unwrap(), entries);
+        let err = save_catalog(&path, &entries[..1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(load_catalog(&path).unwrap().len(), entries.len());
+    }
+}
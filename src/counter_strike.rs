@@ -1,63 +1,53 @@
-use crate::steam;
-use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
-use std::process::{Child, Command};
+use crate::convars;
+use crate::game::GameDefinition;
 
 pub const CS2APPID: &u32 = &730;
-pub const MAP_EXT: &str = &".vpk";
-
-pub const MAPS_DIR_PATH: &str = &"game/csgo/maps";
-const BINARY_DIR_PATH: &str = &"game/bin/win64";
-const BINARY_NAME: &str = &"cs2.exe";
-
-pub fn get_available_maps(game_path: &String) -> Option<Vec<String>> {
-    let maps_dir = Path::new(game_path).join(MAPS_DIR_PATH);
-
-    return match fs::read_dir(maps_dir) {
-        Ok(files) => Some(
-            files
-                .into_iter()
-                .filter(|r| r.is_ok())
-                .map(|r| r.unwrap().file_name().into_string().unwrap())
-                .filter(|r| r.ends_with(MAP_EXT))
-                .map(|r| r.replace(MAP_EXT, ""))
-                .collect(),
-        ),
-        Err(_) => None,
-    };
-}
+const MAP_EXT: &str = &".vpk";
 
-pub fn get_maps_dir(game_path: &String) -> String {
-    return String::from(
-        Path::new(game_path)
-            .join(MAPS_DIR_PATH)
-            .canonicalize()
-            .unwrap()
-            .to_str()
-            .unwrap(),
-    );
-}
+const CONTENT_DIR_PATH: &str = &"game/csgo";
+const GAMEINFO_NAME: &str = &"gameinfo.gi";
+const MAPS_DIR_PATH: &str = &"game/csgo/maps";
+const WINDOWS_BINARY_PATH: &str = &"game/bin/win64/cs2.exe";
+const LINUX_BINARY_PATH: &str = &"game/cs2.sh";
 
-pub fn create_server_process(
-    game_path: &String,
-    args: Vec<String>,
-    envs: HashMap<String, String>,
-) -> Result<Child, steam::ServerCreationError> {
-    let game_exe_path = Path::new(game_path).join(BINARY_DIR_PATH).join(BINARY_NAME);
+pub struct CounterStrike2;
+
+impl GameDefinition for CounterStrike2 {
+    fn name(&self) -> &'static str {
+        return "Counter-Strike 2";
+    }
 
-    if !game_exe_path.exists() {
-        return Err(steam::ServerCreationError::NoExecutableFound);
+    fn app_id(&self) -> u32 {
+        return *CS2APPID;
     }
 
-    if !cfg!(target_os = "windows") {
-        return Err(steam::ServerCreationError::UnsupportedOS);
+    fn binary_path(&self) -> Option<&'static str> {
+        if cfg!(target_os = "windows") {
+            return Some(WINDOWS_BINARY_PATH);
+        }
+        if cfg!(target_os = "linux") {
+            return Some(LINUX_BINARY_PATH);
+        }
+        return None;
     }
 
-    let process = Command::new(game_exe_path.canonicalize().unwrap())
-        .envs(envs)
-        .args(args)
-        .spawn()
-        .expect("something went wrongly...");
-    return Ok(process);
+    fn content_dir(&self) -> &'static str {
+        return CONTENT_DIR_PATH;
+    }
+
+    fn gameinfo_name(&self) -> &'static str {
+        return GAMEINFO_NAME;
+    }
+
+    fn maps_dir(&self) -> &'static str {
+        return MAPS_DIR_PATH;
+    }
+
+    fn map_extension(&self) -> &'static str {
+        return MAP_EXT;
+    }
+
+    fn convar_catalog(&self) -> &'static str {
+        return convars::BUNDLED_CATALOG;
+    }
 }
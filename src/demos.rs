@@ -0,0 +1,431 @@
+use crate::utils;
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const DEMO_EXT: &str = &"dem";
+const DEMO_MAGIC: &[u8] = b"PBDEMS2\0";
+// file header is the first frame, it is small and never compressed
+const HEADER_READ_BYTES: u64 = 64 * 1024;
+const FILE_INFO_READ_BYTES: u64 = 16 * 1024;
+
+// EDemoCommands from demo.proto
+const DEM_FILE_HEADER: u64 = 1;
+const DEM_FILE_INFO: u64 = 2;
+const DEM_IS_COMPRESSED: u64 = 64;
+
+// Keeps only characters safe in file names and console commands
+pub fn sanitize(value: &str) -> String {
+    return value
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+}
+
+// e.g. `2024-01-31_20-15_de_dust2_Navi-vs-G2`, passed to `tv_record`
+pub fn demo_name(started: SystemTime, map: &str, team1: &str, team2: &str) -> String {
+    let date = utils::format_utc(started);
+    let date = date
+        .get(..16)
+        .unwrap_or(&date)
+        .replace(' ', "_")
+        .replace(':', "-");
+    let team_or = |name: &str, default: &str| {
+        if name.trim().is_empty() {
+            String::from(default)
+        } else {
+            sanitize(name)
+        }
+    };
+    return format!(
+        "{date}_{}_{}-vs-{}",
+        sanitize(map),
+        team_or(team1, "CT"),
+        team_or(team2, "T")
+    );
+}
+
+#[derive(Default, Clone)]
+pub struct DemoHeader {
+    pub map: String,
+    pub server_name: String,
+    pub build: i32,
+    // None if file info is missing, e.g. recording was interrupted
+    pub playback_secs: Option<f32>,
+}
+
+pub struct DemoFile {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub modified: SystemTime,
+    pub header: Option<DemoHeader>,
+}
+
+fn invalid(message: &str) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, String::from(message));
+}
+
+// (number, wire type, varint value or fixed bytes)
+type Field<'a> = (u64, u64, u64, &'a [u8]);
+
+struct ProtoReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ProtoReader<'a> {
+    fn varint(&mut self) -> io::Result<u64> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self
+                .data
+                .split_first()
+                .ok_or_else(|| invalid("truncated varint"))?;
+            self.data = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        return Err(invalid("varint is too long"));
+    }
+
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(invalid("truncated field"));
+        }
+        let (value, rest) = self.data.split_at(len);
+        self.data = rest;
+        return Ok(value);
+    }
+
+    fn field(&mut self) -> io::Result<Option<Field<'a>>> {
+        if self.data.is_empty() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let (number, wire_type) = (key >> 3, key & 7);
+        return match wire_type {
+            0 => Ok(Some((number, wire_type, self.varint()?, &[]))),
+            1 => Ok(Some((number, wire_type, 0, self.bytes(8)?))),
+            2 => {
+                let len = self.varint()? as usize;
+                Ok(Some((number, wire_type, 0, self.bytes(len)?)))
+            }
+            5 => Ok(Some((number, wire_type, 0, self.bytes(4)?))),
+            _ => Err(invalid("unsupported wire type")),
+        };
+    }
+}
+
+// Reads `cmd, tick, size` frame prefix, returns command and payload
+fn read_frame(data: &[u8]) -> io::Result<(u64, &[u8])> {
+    let mut reader = ProtoReader { data };
+    let command = reader.varint()?;
+    let _tick = reader.varint()?;
+    let size = reader.varint()? as usize;
+    return Ok((command, reader.bytes(size)?));
+}
+
+// CDemoFileHeader: 3 - server_name, 5 - map_name, 13 - build_num
+fn parse_file_header(payload: &[u8], header: &mut DemoHeader) -> io::Result<()> {
+    let mut reader = ProtoReader { data: payload };
+    while let Some((number, _, value, bytes)) = reader.field()? {
+        match number {
+            3 => header.server_name = String::from_utf8_lossy(bytes).into_owned(),
+            5 => header.map = String::from_utf8_lossy(bytes).into_owned(),
+            13 => header.build = value as i32,
+            _ => {}
+        }
+    }
+    return Ok(());
+}
+
+// CDemoFileInfo: 1 - playback_time as float
+fn parse_file_info(payload: &[u8]) -> io::Result<Option<f32>> {
+    let mut reader = ProtoReader { data: payload };
+    while let Some((number, wire_type, _, bytes)) = reader.field()? {
+        if number == 1 && wire_type == 5 {
+            return Ok(Some(f32::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ])));
+        }
+    }
+    return Ok(None);
+}
+
+fn read_at(file: &mut File, offset: u64, limit: u64) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    file.seek(SeekFrom::Start(offset))?;
+    file.take(limit).read_to_end(&mut data)?;
+    return Ok(data);
+}
+
+// Layout: `PBDEMS2\0`, i32 offset of file info frame, i32 spare, then frames
+pub fn read_header(path: &Path) -> io::Result<DemoHeader> {
+    let mut file = File::open(path)?;
+    let data = read_at(&mut file, 0, HEADER_READ_BYTES)?;
+    if data.len() < 16 || &data[..8] != DEMO_MAGIC {
+        return Err(invalid("not a CS2 demo"));
+    }
+    let file_info_offset = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+
+    let (command, payload) = read_frame(&data[16..])?;
+    if command & !DEM_IS_COMPRESSED != DEM_FILE_HEADER || command & DEM_IS_COMPRESSED != 0 {
+        return Err(invalid("demo has no file header"));
+    }
+    let mut header = DemoHeader::default();
+    parse_file_header(payload, &mut header)?;
+
+    if file_info_offset > 0 {
+        let data = read_at(&mut file, u64::from(file_info_offset), FILE_INFO_READ_BYTES)?;
+        // compressed file info is skipped, duration is only nice to have
+        if let Ok((DEM_FILE_INFO, payload)) = read_frame(&data) {
+            header.playback_secs = parse_file_info(payload).ok().flatten();
+        }
+    }
+    return Ok(header);
+}
+
+// Demos in given directory, newest first
+pub fn list(dir: &Path) -> Vec<DemoFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut demos: Vec<DemoFile> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == DEMO_EXT))
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            let path = e.path();
+            return Some(DemoFile {
+                name: e.file_name().to_string_lossy().into_owned(),
+                size: metadata.len(),
+                modified: metadata.modified().ok()?,
+                header: read_header(&path)
+                    .map_err(|err| log::debug!("Unable to read {}: {err}", path.display()))
+                    .ok(),
+                path,
+            });
+        })
+        .collect();
+    demos.sort_by_key(|d| Reverse(d.modified));
+    return demos;
+}
+
+// Keeps `.dem` extension so the game can still find renamed demo, `playdemo` can't take spaces
+pub fn rename(path: &Path, new_name: &str) -> io::Result<PathBuf> {
+    let new_name = new_name.trim().trim_end_matches(".dem");
+    if new_name.is_empty() || new_name.contains(['/', '\\', ' ']) {
+        return Err(invalid("invalid demo name"));
+    }
+    let target = path.with_file_name(format!("{new_name}.{DEMO_EXT}"));
+    if target.exists() {
+        return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+    }
+    fs::rename(path, &target)?;
+    return Ok(target);
+}
+
+// Falls back to copy and delete when target is on another drive
+pub fn move_to(path: &Path, dir: &Path) -> io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| invalid("invalid demo path"))?;
+    fs::create_dir_all(dir)?;
+    let target = dir.join(file_name);
+    if target.exists() {
+        return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+    }
+    if fs::rename(path, &target).is_err() {
+        fs::copy(path, &target)?;
+        fs::remove_file(path)?;
+    }
+    return Ok(target);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("demos_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn string_field(number: u64, value: &str) -> Vec<u8> {
+        let mut bytes = varint(number << 3 | 2);
+        bytes.extend(varint(value.len() as u64));
+        bytes.extend(value.as_bytes());
+        return bytes;
+    }
+
+    fn frame(command: u64, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = varint(command);
+        bytes.extend(varint(0));
+        bytes.extend(varint(payload.len() as u64));
+        bytes.extend(payload);
+        return bytes;
+    }
+
+    fn file_header() -> Vec<u8> {
+        let mut payload = string_field(3, "Prestarter LAN");
+        // unknown fields in between are skipped
+        payload.extend(string_field(4, "Source TV"));
+        payload.extend(string_field(5, "de_dust2"));
+        payload.extend(varint(13 << 3));
+        payload.extend(varint(10_035));
+        return payload;
+    }
+
+    // `file_info_offset` None points file info past the end of demo
+    fn demo(header_command: u64, header: &[u8], playback_secs: Option<f32>) -> Vec<u8> {
+        let mut frames = frame(header_command, header);
+        // packet frames recorded during match
+        frames.extend(frame(7, &[0; 32]));
+        let info_offset = 16 + frames.len();
+        if let Some(secs) = playback_secs {
+            let mut info = varint(1 << 3 | 5);
+            info.extend(secs.to_le_bytes());
+            frames.extend(frame(DEM_FILE_INFO, &info));
+        }
+
+        let mut data = Vec::from(DEMO_MAGIC);
+        let offset = match playback_secs {
+            Some(_) => info_offset as u32,
+            None => info_offset as u32 + 1024,
+        };
+        data.extend(offset.to_le_bytes());
+        data.extend([0; 4]);
+        data.extend(frames);
+        return data;
+    }
+
+    fn read(dir: &Path, name: &str, data: &[u8]) -> io::Result<DemoHeader> {
+        let path = dir.join(name);
+        fs::write(&path, data).unwrap();
+        return read_header(&path);
+    }
+
+    #[test]
+    fn reads_header_and_file_info() {
+        let dir = temp_dir("header");
+        let header = read(
+            &dir,
+            "full.dem",
+            &demo(DEM_FILE_HEADER, &file_header(), Some(1834.5)),
+        )
+        .unwrap();
+        assert_eq!(header.map, "de_dust2");
+        assert_eq!(header.server_name, "Prestarter LAN");
+        assert_eq!(header.build, 10_035);
+        assert_eq!(header.playback_secs, Some(1834.5));
+    }
+
+    #[test]
+    fn file_info_past_end_has_no_duration() {
+        let dir = temp_dir("past_end");
+        let header = read(
+            &dir,
+            "cut.dem",
+            &demo(DEM_FILE_HEADER, &file_header(), None),
+        )
+        .unwrap();
+        assert_eq!(header.map, "de_dust2");
+        assert_eq!(header.playback_secs, None);
+    }
+
+    #[test]
+    fn rejects_invalid_demos() {
+        let dir = temp_dir("invalid");
+
+        let mut wrong_magic = demo(DEM_FILE_HEADER, &file_header(), Some(1.0));
+        wrong_magic[..8].copy_from_slice(b"HL2DEMO\0");
+        assert!(read(&dir, "magic.dem", &wrong_magic).is_err());
+
+        let compressed = demo(
+            DEM_FILE_HEADER | DEM_IS_COMPRESSED,
+            &file_header(),
+            Some(1.0),
+        );
+        assert!(read(&dir, "compressed.dem", &compressed).is_err());
+
+        // varint continuation bit set on last byte
+        let mut truncated_varint = Vec::from(DEMO_MAGIC);
+        truncated_varint.extend([0; 8]);
+        truncated_varint.extend([DEM_FILE_HEADER as u8, 0x80]);
+        assert!(read(&dir, "varint.dem", &truncated_varint).is_err());
+
+        // frame size is bigger than what is left of file
+        let full = demo(DEM_FILE_HEADER, &file_header(), None);
+        let truncated_frame = &full[..16 + 10];
+        assert!(read(&dir, "frame.dem", truncated_frame).is_err());
+
+        // string field length runs past end of header payload
+        let mut truncated_field = string_field(5, "de_dust2");
+        truncated_field.truncate(5);
+        let truncated_field = demo(DEM_FILE_HEADER, &truncated_field, None);
+        assert!(read(&dir, "field.dem", &truncated_field).is_err());
+
+        assert!(read(&dir, "short.dem", DEMO_MAGIC).is_err());
+    }
+
+    #[test]
+    fn names_demo_after_match() {
+        let started = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(
+            demo_name(started, "de_dust2", "G2 Esports", ""),
+            "2023-11-14_22-13_de_dust2_G2_Esports-vs-T"
+        );
+        assert_eq!(
+            demo_name(started, "workshop/123/de_x", "", " "),
+            "2023-11-14_22-13_workshop_123_de_x_CT-vs-T"
+        );
+    }
+
+    #[test]
+    fn rename_checks_name_and_target() {
+        let dir = temp_dir("rename");
+        let path = dir.join("auto0.dem");
+        fs::write(&path, DEMO_MAGIC).unwrap();
+        fs::write(dir.join("taken.dem"), DEMO_MAGIC).unwrap();
+
+        assert!(rename(&path, "final match").is_err());
+        assert!(rename(&path, "../outside").is_err());
+        assert!(rename(&path, "").is_err());
+        assert_eq!(
+            rename(&path, "taken").unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+
+        let renamed = rename(&path, "final.dem").unwrap();
+        assert_eq!(renamed, dir.join("final.dem"));
+        assert!(renamed.exists() && !path.exists());
+    }
+}
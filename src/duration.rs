@@ -0,0 +1,186 @@
+// Convars measuring time in minutes, everything else is expected in seconds
+const MINUTE_CONVARS: &[&str] = &[
+    "mp_roundtime",
+    "mp_roundtime_defuse",
+    "mp_roundtime_hostage",
+];
+
+// Enough precision for minutes to survive round trip through whole seconds
+const MINUTES_PRECISION: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Seconds,
+    Minutes,
+}
+
+impl TimeUnit {
+    pub fn for_convar(name: &str) -> TimeUnit {
+        return if MINUTE_CONVARS.contains(&name) {
+            TimeUnit::Minutes
+        } else {
+            TimeUnit::Seconds
+        };
+    }
+}
+
+// Duration with whole-second resolution, persisted as plain number of seconds
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[serde(transparent)]
+pub struct ConvarDuration(u32);
+
+impl ConvarDuration {
+    pub fn from_secs(seconds: u32) -> ConvarDuration {
+        return ConvarDuration(seconds);
+    }
+
+    pub fn as_secs(self) -> u32 {
+        return self.0;
+    }
+
+    pub fn to_mm_ss(self) -> String {
+        return format!("{}:{:02}", self.0 / 60, self.0 % 60);
+    }
+
+    // Accepts `m:ss` as well as bare number of seconds
+    pub fn parse_mm_ss(text: &str) -> Option<ConvarDuration> {
+        let text = text.trim();
+
+        return match text.split_once(':') {
+            None => text.parse::<u32>().ok().map(ConvarDuration),
+            Some((minutes, seconds)) => {
+                let minutes = minutes.trim().parse::<u32>().ok()?;
+                let seconds = seconds.trim().parse::<u32>().ok()?;
+                if seconds >= 60 {
+                    return None;
+                }
+                minutes
+                    .checked_mul(60)
+                    .and_then(|m| m.checked_add(seconds))
+                    .map(ConvarDuration)
+            }
+        };
+    }
+
+    pub fn to_convar_value(self, unit: TimeUnit) -> String {
+        return match unit {
+            TimeUnit::Seconds => self.0.to_string(),
+            TimeUnit::Minutes => {
+                let minutes = format!("{:.*}", MINUTES_PRECISION, self.0 as f64 / 60.0);
+                String::from(minutes.trim_end_matches('0').trim_end_matches('.'))
+            }
+        };
+    }
+
+    // Inverse of `to_convar_value`, only needed to check the round trip
+    #[cfg(test)]
+    pub fn from_convar_value(value: &str, unit: TimeUnit) -> Option<ConvarDuration> {
+        let value: f64 = value.trim().parse().ok()?;
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+
+        let seconds = match unit {
+            TimeUnit::Seconds => value,
+            TimeUnit::Minutes => value * 60.0,
+        };
+        return Some(ConvarDuration(seconds.round() as u32));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mm_ss_round_trip() {
+        for seconds in [0, 5, 59, 60, 115, 3600, 5999] {
+            let duration = ConvarDuration::from_secs(seconds);
+            assert_eq!(
+                ConvarDuration::parse_mm_ss(&duration.to_mm_ss()),
+                Some(duration)
+            );
+        }
+        assert_eq!(ConvarDuration::from_secs(115).to_mm_ss(), "1:55");
+        assert_eq!(ConvarDuration::from_secs(5).to_mm_ss(), "0:05");
+    }
+
+    #[test]
+    fn parses_mm_ss_and_bare_seconds() {
+        assert_eq!(
+            ConvarDuration::parse_mm_ss(" 2:05 "),
+            Some(ConvarDuration::from_secs(125))
+        );
+        assert_eq!(
+            ConvarDuration::parse_mm_ss("90"),
+            Some(ConvarDuration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_mm_ss() {
+        for text in [
+            "", " ", ":", "1:", ":30", "1:60", "1:75", "a:10", "1:2b", "-1:00", "1.5",
+        ] {
+            assert_eq!(ConvarDuration::parse_mm_ss(text), None, "{text:?}");
+        }
+        assert_eq!(ConvarDuration::parse_mm_ss("99999999:00"), None);
+    }
+
+    #[test]
+    fn convar_value_in_seconds() {
+        let duration = ConvarDuration::from_secs(40);
+        assert_eq!(duration.to_convar_value(TimeUnit::Seconds), "40");
+        assert_eq!(
+            ConvarDuration::from_convar_value("40", TimeUnit::Seconds),
+            Some(duration)
+        );
+        assert_eq!(
+            ConvarDuration::from_convar_value("-1", TimeUnit::Seconds),
+            None
+        );
+        assert_eq!(
+            ConvarDuration::from_convar_value("", TimeUnit::Seconds),
+            None
+        );
+    }
+
+    #[test]
+    fn convar_value_in_minutes() {
+        assert_eq!(TimeUnit::for_convar("mp_roundtime"), TimeUnit::Minutes);
+        assert_eq!(TimeUnit::for_convar("mp_freezetime"), TimeUnit::Seconds);
+
+        assert_eq!(
+            ConvarDuration::from_secs(120).to_convar_value(TimeUnit::Minutes),
+            "2"
+        );
+        assert_eq!(
+            ConvarDuration::from_secs(115).to_convar_value(TimeUnit::Minutes),
+            "1.9167"
+        );
+        for seconds in [0, 1, 59, 60, 115, 137, 3600] {
+            let duration = ConvarDuration::from_secs(seconds);
+            let value = duration.to_convar_value(TimeUnit::Minutes);
+            assert_eq!(
+                ConvarDuration::from_convar_value(&value, TimeUnit::Minutes),
+                Some(duration),
+                "{value}"
+            );
+        }
+        assert_eq!(
+            ConvarDuration::from_convar_value("nan", TimeUnit::Minutes),
+            None
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::counter_strike::CounterStrike2;
+use crate::steam;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+const STEAM_INF_NAME: &str = &"steam.inf";
+const MOTD_NAME: &str = &"motd.txt";
+// relative to content dir, CounterStrikeSharp loads each plugin from own directory
+const ADMIN_PLUGIN_DIR: &str = &"addons/counterstrikesharp/plugins/CS2-SimpleAdmin";
+
+// Layout of a Source dedicated server install, paths are relative to game root directory
+pub trait GameDefinition {
+    fn name(&self) -> &'static str;
+
+    fn app_id(&self) -> u32;
+
+    // None if server can't run on current OS
+    fn binary_path(&self) -> Option<&'static str>;
+
+    // Mod directory with gameinfo, steam.inf and motd.txt, e.g. `game/csgo`
+    fn content_dir(&self) -> &'static str;
+
+    fn gameinfo_name(&self) -> &'static str;
+
+    fn maps_dir(&self) -> &'static str;
+
+    fn map_extension(&self) -> &'static str;
+
+    // Bundled `cvarlist` output, see `convars::parse_cvarlist`
+    fn convar_catalog(&self) -> &'static str;
+
+    fn connect_url(&self, address: &str, port: u16, password: &str) -> String {
+        return format!("steam://connect/{address}:{port}/{password}");
+    }
+
+    // `demo` is relative to content dir, without extension
+    fn play_demo_url(&self, demo: &str) -> String {
+        return format!("steam://rungameid/{}//+playdemo%20{demo}", self.app_id());
+    }
+}
+
+const GAMES: &[&dyn GameDefinition] = &[&CounterStrike2];
+
+pub fn definition_for_app(app_id: u32) -> Option<&'static dyn GameDefinition> {
+    return GAMES.iter().copied().find(|g| g.app_id() == app_id);
+}
+
+#[derive(Clone, Default)]
+pub struct GameVersion {
+    pub client_version: String,
+    pub server_version: String,
+    pub patch_version: String,
+}
+
+impl GameVersion {
+    pub fn read(game: &dyn GameDefinition, game_path: &String) -> Option<GameVersion> {
+        let path = Path::new(game_path)
+            .join(game.content_dir())
+            .join(STEAM_INF_NAME);
+        let content = fs::read_to_string(path).ok()?;
+        return Some(GameVersion::parse(&content));
+    }
+
+    // steam.inf is a list of `Key=Value` lines
+    pub fn parse(content: &str) -> GameVersion {
+        let mut version = GameVersion::default();
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = String::from(value.trim());
+            match key.trim() {
+                "ClientVersion" => version.client_version = value,
+                "ServerVersion" => version.server_version = value,
+                "PatchVersion" => version.patch_version = value,
+                _ => {}
+            }
+        }
+        return version;
+    }
+}
+
+#[derive(Clone)]
+pub struct InstallCheck {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub found: bool,
+}
+
+pub fn check_install(game: &dyn GameDefinition, game_path: &String) -> Vec<InstallCheck> {
+    let root = Path::new(game_path);
+    let binary = match game.binary_path() {
+        Some(res) => root.join(res),
+        None => PathBuf::new(),
+    };
+
+    return [
+        (
+            "Game info",
+            root.join(game.content_dir()).join(game.gameinfo_name()),
+        ),
+        ("Server binary", binary),
+        ("Maps directory", root.join(game.maps_dir())),
+    ]
+    .into_iter()
+    .map(|(name, path)| InstallCheck {
+        name,
+        found: !path.as_os_str().is_empty() && path.exists(),
+        path,
+    })
+    .collect();
+}
+
+pub fn get_available_maps(game: &dyn GameDefinition, game_path: &String) -> Option<Vec<String>> {
+    let maps_dir = Path::new(game_path).join(game.maps_dir());
+    let map_ext = game.map_extension();
+
+    return match fs::read_dir(maps_dir) {
+        Ok(files) => Some(
+            files
+                .into_iter()
+                .filter_map(|r| r.ok())
+                .filter_map(|r| r.file_name().into_string().ok())
+                .filter(|r| r.ends_with(map_ext))
+                .map(|r| r.replace(map_ext, ""))
+                .collect(),
+        ),
+        Err(_) => None,
+    };
+}
+
+pub fn get_maps_dir(game: &dyn GameDefinition, game_path: &String) -> io::Result<PathBuf> {
+    return Path::new(game_path).join(game.maps_dir()).canonicalize();
+}
+
+pub fn write_motd(game: &dyn GameDefinition, game_path: &String, motd: &str) -> io::Result<()> {
+    let path = Path::new(game_path)
+        .join(game.content_dir())
+        .join(MOTD_NAME);
+    return fs::write(path, motd);
+}
+
+// CS2-SimpleAdmin provides commands to move and mute single player
+pub fn has_admin_plugin(game: &dyn GameDefinition, game_path: &String) -> bool {
+    return Path::new(game_path)
+        .join(game.content_dir())
+        .join(ADMIN_PLUGIN_DIR)
+        .is_dir();
+}
+
+pub fn create_server_process(
+    game: &dyn GameDefinition,
+    game_path: &String,
+    args: Vec<String>,
+    envs: HashMap<String, String>,
+) -> Result<Child, steam::ServerCreationError> {
+    let binary_path = match game.binary_path() {
+        Some(res) => res,
+        None => return Err(steam::ServerCreationError::UnsupportedOS),
+    };
+    let game_exe_path = Path::new(game_path).join(binary_path);
+
+    if !game_exe_path.exists() {
+        return Err(steam::ServerCreationError::NoExecutableFound);
+    }
+
+    let game_exe_path = game_exe_path
+        .canonicalize()
+        .map_err(steam::ServerCreationError::SpawnFailed)?;
+
+    return Command::new(game_exe_path)
+        .envs(envs)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(steam::ServerCreationError::SpawnFailed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEAM_INF: &str = include_str!("../testdata/steam.inf");
+
+    #[test]
+    fn parses_steam_inf() {
+        let version = GameVersion::parse(STEAM_INF);
+        assert_eq!(version.client_version, "2000623");
+        assert_eq!(version.server_version, "2000623");
+        assert_eq!(version.patch_version, "1.41.0.3");
+    }
+
+    #[test]
+    fn missing_keys_stay_empty() {
+        let version = GameVersion::parse("PatchVersion = 1.41.0.3\ngarbage line\n");
+        assert_eq!(version.patch_version, "1.41.0.3");
+        assert!(version.client_version.is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::game::{self, GameDefinition, GameVersion, InstallCheck};
+
+// Game copy registered by user in addition to one found by Steam detection
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Installation {
+    pub label: String,
+    pub path: String,
+}
+
+pub struct InstallationDetails {
+    pub version: Option<GameVersion>,
+    pub maps: Vec<String>,
+    pub checks: Vec<InstallCheck>,
+}
+
+impl InstallationDetails {
+    pub fn is_complete(&self) -> bool {
+        return self.checks.iter().all(|c| c.found);
+    }
+}
+
+pub fn find<'a>(installations: &'a [Installation], label: &str) -> Option<&'a Installation> {
+    return installations.iter().find(|i| i.label == label);
+}
+
+// Adds new installation or updates path of existing one with the same label
+pub fn register(installations: &mut Vec<Installation>, label: &str, path: &str) {
+    match installations.iter_mut().find(|i| i.label == label) {
+        Some(existing) => existing.path = String::from(path),
+        None => installations.push(Installation {
+            label: String::from(label),
+            path: String::from(path),
+        }),
+    }
+}
+
+pub fn inspect(game: &dyn GameDefinition, installation: &Installation) -> InstallationDetails {
+    return InstallationDetails {
+        version: GameVersion::read(game, &installation.path),
+        maps: game::get_available_maps(game, &installation.path).unwrap_or_default(),
+        checks: game::check_install(game, &installation.path),
+    };
+}
@@ -0,0 +1,186 @@
+// Minimal reader for Valve KeyValues text format used by `.acf` manifests and round backups
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Str(String),
+    Section(KeyValues),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeyValues {
+    pub entries: Vec<(String, Value)>,
+}
+
+impl KeyValues {
+    // Keys are case-insensitive, first match wins
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        return self
+            .entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v);
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        return match self.get(key)? {
+            Value::Str(value) => Some(value.as_str()),
+            Value::Section(_) => None,
+        };
+    }
+
+    pub fn get_section(&self, key: &str) -> Option<&KeyValues> {
+        return match self.get(key)? {
+            Value::Section(section) => Some(section),
+            Value::Str(_) => None,
+        };
+    }
+
+    pub fn get_parsed<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        return self.get_str(key)?.trim().parse().ok();
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            'n' => value.push('\n'),
+                            't' => value.push('\t'),
+                            other => value.push(other),
+                        },
+                        other => value.push(other),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for skipped in chars.by_ref() {
+                    if skipped == '\n' {
+                        break;
+                    }
+                }
+            }
+            // platform conditionals like [$WIN32] are ignored
+            '[' => {
+                for skipped in chars.by_ref() {
+                    if skipped == ']' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut value = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || next == '{' || next == '}' || next == '"' {
+                        break;
+                    }
+                    value.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Str(value));
+            }
+        }
+    }
+
+    return Some(tokens);
+}
+
+fn parse_section(tokens: &mut std::vec::IntoIter<Token>, nested: bool) -> Option<KeyValues> {
+    let mut section = KeyValues::default();
+
+    loop {
+        let key = match tokens.next() {
+            None if !nested => return Some(section),
+            Some(Token::Close) if nested => return Some(section),
+            Some(Token::Str(key)) => key,
+            _ => return None,
+        };
+
+        let value = match tokens.next()? {
+            Token::Str(value) => Value::Str(value),
+            Token::Open => Value::Section(parse_section(tokens, true)?),
+            Token::Close => return None,
+        };
+        section.entries.push((key, value));
+    }
+}
+
+// Returns None for malformed input like unbalanced braces or unterminated strings
+pub fn parse(text: &str) -> Option<KeyValues> {
+    let mut tokens = tokenize(text)?.into_iter();
+    return parse_section(&mut tokens, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APP_MANIFEST: &str = include_str!("../testdata/appmanifest_730.acf");
+
+    #[test]
+    fn parses_app_manifest() {
+        let root = parse(APP_MANIFEST).unwrap();
+        let state = root.get_section("appstate").unwrap();
+        assert_eq!(state.get_str("name"), Some("Counter-Strike 2"));
+        assert_eq!(state.get_parsed::<u32>("StateFlags"), Some(6));
+        assert_eq!(
+            state.get_str("LauncherPath"),
+            Some("C:\\Program Files (x86)\\Steam\\steam.exe")
+        );
+
+        let depots = state.get_section("InstalledDepots").unwrap();
+        assert_eq!(depots.entries.len(), 2);
+        assert_eq!(
+            depots
+                .get_section("2347773")
+                .and_then(|d| d.get_parsed::<u64>("size")),
+            Some(35148212435)
+        );
+        assert_eq!(state.get_section("appid"), None);
+    }
+
+    #[test]
+    fn skips_comments_and_conditionals() {
+        let root = parse(
+            "// saved by round backup\n\
+             \"Root\" {\n\
+                 \"path\" \"D:\\\\Games\\\\cs2\" [$WIN32] // trailing comment\n\
+                 \"path\" \"/home/steam/cs2\" [$LINUX]\n\
+                 unquoted value\n\
+             }",
+        )
+        .unwrap();
+        let section = root.get_section("Root").unwrap();
+        assert_eq!(section.entries.len(), 3);
+        assert_eq!(section.get_str("path"), Some("D:\\Games\\cs2"));
+        assert_eq!(section.get_str("unquoted"), Some("value"));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(parse("\"Root\" { \"key\" \"value\""), None);
+        assert_eq!(parse("\"Root\" { \"key\" \"value\" } }"), None);
+        assert_eq!(parse("\"Root\" { \"key\" \"value }"), None);
+        assert_eq!(parse("\"Root\" { \"key\" }"), None);
+        assert_eq!(parse("\"key\""), None);
+        assert_eq!(parse(""), Some(KeyValues::default()));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::duration::{ConvarDuration, TimeUnit};
+use crate::settings::ServerSettings;
+use crate::utils;
+use std::collections::HashMap;
+
+fn push_convar(args: &mut Vec<String>, name: &str, value: String) {
+    args.extend_from_slice(&[format!("+{name}"), value]);
+}
+
+fn push_duration(args: &mut Vec<String>, name: &str, value: ConvarDuration) {
+    push_convar(
+        args,
+        name,
+        value.to_convar_value(TimeUnit::for_convar(name)),
+    );
+}
+
+// `log_address` is URL of embedded log receiver, registered with `logaddress_add_http`
+pub fn build_args(settings: &ServerSettings, log_address: Option<&str>) -> Vec<String> {
+    let mut args: Vec<String> = Vec::new();
+    args.push(String::from("-dedicated"));
+    if settings.insecure {
+        args.push(String::from("-insecure"));
+    }
+    args.extend_from_slice(&[String::from("-maxplayers"), settings.maxplayers.to_string()]);
+    if !settings.sv_setsteamaccount.is_empty() {
+        push_convar(
+            &mut args,
+            "sv_setsteamaccount",
+            String::from(&settings.sv_setsteamaccount),
+        );
+    }
+
+    push_convar(&mut args, "hostname", String::from(&settings.hostname));
+    push_convar(&mut args, "sv_tags", String::from(&settings.sv_tags));
+    push_convar(&mut args, "sv_region", settings.sv_region.to_string());
+    push_convar(&mut args, "sv_lan", utils::bool_to_str(settings.sv_lan));
+    push_convar(
+        &mut args,
+        "sv_visiblemaxplayers",
+        settings.sv_visiblemaxplayers.to_string(),
+    );
+
+    for (name, value) in [
+        ("mp_teamname_1", &settings.mp_teamname_1),
+        ("mp_teamname_2", &settings.mp_teamname_2),
+    ] {
+        if !value.is_empty() {
+            push_convar(&mut args, name, String::from(value));
+        }
+    }
+
+    // GOTV does not start if enabled after map is loaded
+    if settings.tv_enable {
+        push_convar(&mut args, "tv_enable", utils::bool_to_str(true));
+        push_duration(&mut args, "tv_delay", settings.tv_delay);
+        push_convar(&mut args, "tv_port", settings.tv_port.to_string());
+        push_convar(&mut args, "tv_name", String::from(&settings.tv_name));
+        push_convar(
+            &mut args,
+            "tv_autorecord",
+            utils::bool_to_str(settings.tv_autorecord),
+        );
+        push_convar(
+            &mut args,
+            "tv_maxclients",
+            settings.tv_maxclients.to_string(),
+        );
+    }
+
+    push_convar(&mut args, "map", String::from(&settings.map_name));
+    push_convar(
+        &mut args,
+        "mp_autokick",
+        utils::bool_to_str(settings.mp_autokick),
+    );
+    push_convar(
+        &mut args,
+        "mp_buy_anywhere",
+        settings.mp_buy_anywhere.to_string(),
+    );
+    push_duration(&mut args, "mp_buytime", settings.mp_buytime);
+    push_duration(&mut args, "mp_c4timer", settings.mp_c4timer);
+    push_duration(&mut args, "mp_freezetime", settings.mp_freezetime);
+    push_convar(
+        &mut args,
+        "mp_friendlyfire",
+        utils::bool_to_str(settings.mp_friendlyfire),
+    );
+    push_convar(&mut args, "mp_maxrounds", settings.mp_maxrounds.to_string());
+    push_convar(
+        &mut args,
+        "mp_randomspawn",
+        settings.mp_randomspawn.to_string(),
+    );
+    push_duration(&mut args, "mp_roundtime", settings.mp_roundtime);
+    push_duration(
+        &mut args,
+        "mp_roundtime_defuse",
+        settings.mp_roundtime_defuse,
+    );
+    push_duration(
+        &mut args,
+        "mp_roundtime_hostage",
+        settings.mp_roundtime_hostage,
+    );
+    push_duration(&mut args, "mp_warmuptime", settings.mp_warmuptime);
+    push_convar(
+        &mut args,
+        "mp_endwarmup_player_count",
+        settings.mp_endwarmup_player_count.to_string(),
+    );
+    push_convar(
+        &mut args,
+        "sv_minupdaterate",
+        settings.sv_minupdaterate.to_string(),
+    );
+    push_convar(
+        &mut args,
+        "sv_password",
+        String::from(&settings.sv_password),
+    );
+
+    // match events for scoreboard are parsed from log echoed to console
+    push_convar(&mut args, "log", String::from("on"));
+    push_convar(&mut args, "sv_logecho", String::from("1"));
+    push_convar(&mut args, "mp_logdetail", String::from("3"));
+    if let Some(address) = log_address {
+        push_convar(&mut args, "logaddress_add_http", String::from(address));
+    }
+
+    return args;
+}
+
+pub fn build_envs(settings: &ServerSettings) -> HashMap<String, String> {
+    let mut envs: HashMap<String, String> = HashMap::new();
+    envs.insert(
+        String::from("game_alias"),
+        String::from(&settings.game_alias),
+    );
+    return envs;
+}
@@ -1,8 +1,31 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod convars;
 mod counter_strike;
+mod demos;
+mod duration;
+mod game;
+mod installations;
+mod keyvalues;
+mod launch;
+mod log_receiver;
+mod match_control;
+mod match_events;
+mod match_export;
+mod players;
+mod readiness;
+mod round_backups;
+mod scanner;
+mod scoreboard;
+mod server;
+mod session_log;
+mod settings;
+mod stats_db;
 mod steam;
+mod steamcmd;
 mod utils;
+mod validation;
+mod watchdog;
 
 pub use app::CS2ServerPrestarterApp;
@@ -0,0 +1,279 @@
+use crate::match_events::LineSource;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(200);
+// bigger requests are not log batches
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+// server sends batches one after another, more is someone else
+const MAX_CONNECTIONS: usize = 16;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct LogReceiverSettings {
+    pub enabled: bool,
+    pub port: u16,
+    // servers on other machines can send logs, otherwise only local ones
+    pub allow_lan: bool,
+}
+
+impl Default for LogReceiverSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 27500,
+            allow_lan: false,
+        }
+    }
+}
+
+// Accepts logs that server POSTs to addresses registered with `logaddress_add_http`
+pub struct LogReceiver {
+    pub port: u16,
+    pub allow_lan: bool,
+    // path servers have to POST to, new for every receiver
+    token: String,
+    lines: Receiver<String>,
+    stopped: Arc<AtomicBool>,
+}
+
+// Random enough to not be guessed by other LAN hosts, std hashers are seeded by OS
+fn session_token() -> String {
+    let mut token = String::new();
+    for _ in 0..2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+        );
+        token.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    return token;
+}
+
+fn read_request(stream: &mut TcpStream, token: &str) -> io::Result<String> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut content_length: usize = 0;
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    if parts.next() != Some("POST") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a POST request",
+        ));
+    }
+    if parts.next() != Some(&format!("/{token}")) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "wrong receiver token",
+        ));
+    }
+
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "request is too big",
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    return Ok(String::from_utf8_lossy(&body).into_owned());
+}
+
+fn handle(mut stream: TcpStream, sender: &Sender<String>, token: &str) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let body = match read_request(&mut stream, token) {
+        Ok(body) => body,
+        Err(err) => {
+            let _ = stream.write_all(
+                b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+            return Err(err);
+        }
+    };
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let _ = sender.send(String::from(line));
+    }
+    stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")?;
+    return stream.flush();
+}
+
+impl LogReceiver {
+    // Listens on all interfaces only when servers on other LAN machines are allowed
+    pub fn start(port: u16, allow_lan: bool) -> io::Result<LogReceiver> {
+        let address = match allow_lan {
+            true => Ipv4Addr::UNSPECIFIED,
+            false => Ipv4Addr::LOCALHOST,
+        };
+        let listener = TcpListener::bind((address, port))?;
+        let port = listener.local_addr()?.port();
+        // polled, so port is released soon after receiver is dropped
+        listener.set_nonblocking(true)?;
+        let (sender, receiver) = mpsc::channel::<String>();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let stop_flag = stopped.clone();
+        let token = session_token();
+        let accepted_token = token.clone();
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        thread::spawn(move || {
+            while !stop_flag.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    // slow client must not hold up other connections
+                    Ok((stream, _)) => {
+                        if connections.load(Ordering::Relaxed) >= MAX_CONNECTIONS {
+                            log::warn!("Too many log connections, dropping one");
+                            continue;
+                        }
+                        connections.fetch_add(1, Ordering::Relaxed);
+                        let connections = connections.clone();
+                        let sender = sender.clone();
+                        let token = accepted_token.clone();
+                        thread::spawn(move || {
+                            if let Err(err) = handle(stream, &sender, &token) {
+                                log::warn!("Unable to receive server log: {err}");
+                            }
+                            connections.fetch_sub(1, Ordering::Relaxed);
+                        });
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        thread::sleep(ACCEPT_INTERVAL);
+                    }
+                    Err(err) => log::warn!("Unable to accept log connection: {err}"),
+                }
+            }
+        });
+
+        return Ok(LogReceiver {
+            port,
+            allow_lan,
+            token,
+            lines: receiver,
+            stopped,
+        });
+    }
+
+    // Address for servers running on the same machine
+    pub fn local_url(&self) -> String {
+        return format!("http://127.0.0.1:{}/{}", self.port, self.token);
+    }
+
+    // Address for servers running on other LAN machines
+    pub fn lan_url(&self) -> Option<String> {
+        if !self.allow_lan {
+            return None;
+        }
+        return lan_address().map(|ip| format!("http://{ip}:{}/{}", self.port, self.token));
+    }
+}
+
+impl Drop for LogReceiver {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl LineSource for LogReceiver {
+    fn read_lines(&mut self) -> Vec<String> {
+        return self.lines.try_iter().collect();
+    }
+}
+
+// Address of interface used for outgoing traffic, connecting UDP socket sends nothing
+fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    return Some(socket.local_addr().ok()?.ip());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(url_path: &str, port: u16, body: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "POST {url_path} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        return response;
+    }
+
+    fn wait_for_lines(receiver: &mut LogReceiver, count: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for _ in 0..50 {
+            lines.extend(receiver.read_lines());
+            if lines.len() >= count {
+                break;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        return lines;
+    }
+
+    #[test]
+    fn accepts_only_session_token() {
+        let mut receiver = LogReceiver::start(0, false).unwrap();
+        let port = receiver.port;
+        let path = format!("/{}", receiver.token);
+
+        let response = post("/", port, "L 10/19/2026 - 20:15:01: spoofed\n");
+        assert!(response.starts_with("HTTP/1.1 403"));
+        let response = post(&path, port, "first\n\nsecond\n");
+        assert!(response.starts_with("HTTP/1.1 200"));
+
+        assert_eq!(wait_for_lines(&mut receiver, 2), ["first", "second"]);
+    }
+
+    #[test]
+    fn slow_client_does_not_block_others() {
+        let mut receiver = LogReceiver::start(0, false).unwrap();
+        let port = receiver.port;
+        let path = format!("/{}", receiver.token);
+
+        // connects but never sends request
+        let _idle = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        post(&path, port, "after idle\n");
+        assert_eq!(wait_for_lines(&mut receiver, 1), ["after idle"]);
+    }
+
+    #[test]
+    fn lan_url_needs_opt_in() {
+        let receiver = LogReceiver::start(0, false).unwrap();
+        assert!(receiver.lan_url().is_none());
+        assert!(receiver.local_url().ends_with(&receiver.token));
+        assert_ne!(receiver.token, LogReceiver::start(0, false).unwrap().token);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::match_events::Team;
+
+// One-click console commands for running match
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchAction {
+    RestartGame,
+    EndWarmup,
+    Pause,
+    Unpause,
+    // uses up one of `mp_team_timeout_max` timeouts of the team
+    TacticalTimeout(Team),
+    // pauses until unpaused without using team timeouts
+    TechnicalTimeout(Team),
+    SwapTeams,
+    ScrambleTeams,
+    ChangeLevel(String),
+    KickBots,
+}
+
+impl MatchAction {
+    pub fn label(&self) -> String {
+        return match self {
+            MatchAction::RestartGame => String::from("Restart game"),
+            MatchAction::EndWarmup => String::from("End warmup"),
+            MatchAction::Pause => String::from("Pause"),
+            MatchAction::Unpause => String::from("Unpause"),
+            MatchAction::TacticalTimeout(team) => {
+                format!("Tactical timeout {}", team.short_name())
+            }
+            MatchAction::TechnicalTimeout(team) => {
+                format!("Technical timeout {}", team.short_name())
+            }
+            MatchAction::SwapTeams => String::from("Swap teams"),
+            MatchAction::ScrambleTeams => String::from("Scramble teams"),
+            MatchAction::ChangeLevel(map) => format!("Change map to {map}"),
+            MatchAction::KickBots => String::from("Kick all bots"),
+        };
+    }
+
+    // `mp_pause_match` takes effect at next freeze time, not immediately
+    pub fn command(&self) -> String {
+        return match self {
+            MatchAction::RestartGame => String::from("mp_restartgame 1"),
+            MatchAction::EndWarmup => String::from("mp_warmup_end"),
+            MatchAction::Pause => String::from("mp_pause_match"),
+            MatchAction::Unpause => String::from("mp_unpause_match"),
+            MatchAction::TacticalTimeout(Team::Terrorist) => {
+                String::from("timeout_terrorist_start")
+            }
+            MatchAction::TacticalTimeout(_) => String::from("timeout_ct_start"),
+            // console has no per-team technical timeout, so pause is announced in chat
+            MatchAction::TechnicalTimeout(team) => format!(
+                "mp_pause_match; say \"Technical timeout: {}\"",
+                team.short_name()
+            ),
+            MatchAction::SwapTeams => String::from("mp_swapteams"),
+            MatchAction::ScrambleTeams => String::from("mp_scrambleteams"),
+            MatchAction::ChangeLevel(map) => format!("changelevel {map}"),
+            MatchAction::KickBots => String::from("bot_kick"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sends_console_commands() {
+        let cases = [
+            (MatchAction::RestartGame, "mp_restartgame 1"),
+            (MatchAction::EndWarmup, "mp_warmup_end"),
+            (MatchAction::Pause, "mp_pause_match"),
+            (MatchAction::Unpause, "mp_unpause_match"),
+            (
+                MatchAction::TacticalTimeout(Team::CounterTerrorist),
+                "timeout_ct_start",
+            ),
+            (
+                MatchAction::TacticalTimeout(Team::Terrorist),
+                "timeout_terrorist_start",
+            ),
+            (
+                MatchAction::TechnicalTimeout(Team::CounterTerrorist),
+                "mp_pause_match; say \"Technical timeout: CT\"",
+            ),
+            (
+                MatchAction::TechnicalTimeout(Team::Terrorist),
+                "mp_pause_match; say \"Technical timeout: T\"",
+            ),
+            (MatchAction::SwapTeams, "mp_swapteams"),
+            (MatchAction::ScrambleTeams, "mp_scrambleteams"),
+            (
+                MatchAction::ChangeLevel(String::from("de_mirage")),
+                "changelevel de_mirage",
+            ),
+            (MatchAction::KickBots, "bot_kick"),
+        ];
+        for (action, command) in cases {
+            assert_eq!(action.command(), command, "{action:?}");
+        }
+    }
+}
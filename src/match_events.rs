@@ -0,0 +1,492 @@
+// Parser for server log lines enabled by `log on`, see https://developer.valvesoftware.com/wiki/HL_Log_Standard
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Team {
+    CounterTerrorist,
+    Terrorist,
+    Spectator,
+    Unassigned,
+}
+
+impl Team {
+    pub fn parse(value: &str) -> Team {
+        return match value {
+            "CT" => Team::CounterTerrorist,
+            "TERRORIST" | "T" => Team::Terrorist,
+            "Spectator" | "SPECTATOR" => Team::Spectator,
+            _ => Team::Unassigned,
+        };
+    }
+
+    pub fn short_name(&self) -> &'static str {
+        return match self {
+            Team::CounterTerrorist => "CT",
+            Team::Terrorist => "T",
+            Team::Spectator => "SPEC",
+            Team::Unassigned => "-",
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub user_id: i32,
+    // `[U:1:123]` or `BOT`
+    pub steam_id: String,
+    pub team: Option<Team>,
+}
+
+impl Player {
+    // Bots have no steam id, so they are told apart by name
+    pub fn key(&self) -> String {
+        if self.steam_id == "BOT" {
+            return format!("BOT {}", self.name);
+        }
+        return self.steam_id.clone();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchEvent {
+    MatchStart {
+        map: String,
+    },
+    RoundStart,
+    RoundEnd {
+        winner: Team,
+        // e.g. `SFUI_Notice_Target_Bombed`, see `win_reason`
+        trigger: String,
+        ct_score: u32,
+        t_score: u32,
+    },
+    Kill {
+        killer: Player,
+        victim: Player,
+        weapon: String,
+        headshot: bool,
+    },
+    Assist {
+        assister: Player,
+        victim: Player,
+    },
+    Damage {
+        attacker: Player,
+        victim: Player,
+        damage: u32,
+        // victim health left after hit
+        health: u32,
+    },
+    BombPlanted {
+        player: Player,
+        site: String,
+    },
+    BombDefused {
+        player: Player,
+    },
+    TeamSwitch {
+        player: Player,
+        from: Team,
+        to: Team,
+    },
+    MatchEnd {
+        mode: String,
+        map: String,
+        ct_score: u32,
+        t_score: u32,
+        minutes: u32,
+    },
+}
+
+impl MatchEvent {
+    // Players mentioned in event, with team they were on at that moment
+    pub fn players(&self) -> Vec<&Player> {
+        return match self {
+            MatchEvent::Kill { killer, victim, .. } => vec![killer, victim],
+            MatchEvent::Assist { assister, victim } => vec![assister, victim],
+            MatchEvent::Damage {
+                attacker, victim, ..
+            } => vec![attacker, victim],
+            MatchEvent::BombPlanted { player, .. }
+            | MatchEvent::BombDefused { player }
+            | MatchEvent::TeamSwitch { player, .. } => vec![player],
+            _ => Vec::new(),
+        };
+    }
+}
+
+// Readable reason of round end trigger
+pub fn win_reason(trigger: &str) -> &str {
+    return match trigger {
+        "SFUI_Notice_Target_Bombed" => "Bomb exploded",
+        "SFUI_Notice_Bomb_Defused" => "Bomb defused",
+        "SFUI_Notice_Target_Saved" => "Time ran out",
+        "SFUI_Notice_CTs_Win" | "SFUI_Notice_Terrorists_Win" => "Elimination",
+        "SFUI_Notice_Hostages_Rescued" => "Hostages rescued",
+        "SFUI_Notice_Hostages_Not_Rescued" => "Hostages not rescued",
+        "SFUI_Notice_CTs_Surrender" | "SFUI_Notice_Terrorists_Surrender" => "Surrender",
+        other => other.trim_start_matches("SFUI_Notice_"),
+    };
+}
+
+// Drops `L 10/19/2026 - 20:15:01: ` prefix of console lines or `10/19/2026 - 20:15:01.123 - ` of HTTP logs
+pub fn strip_timestamp(line: &str) -> &str {
+    let line = line.trim();
+    let rest = line.strip_prefix("L ").unwrap_or(line);
+    let Some((date, rest)) = rest.split_once(" - ") else {
+        return line;
+    };
+    if date.len() != 10 || date.chars().filter(|c| *c == '/').count() != 2 {
+        return line;
+    }
+
+    let time_end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.'))
+        .unwrap_or(rest.len());
+    let rest = &rest[time_end..];
+    return rest
+        .strip_prefix(": ")
+        .or_else(|| rest.strip_prefix(" - "))
+        .unwrap_or(rest)
+        .trim();
+}
+
+// Parses `Name<2><[U:1:123]><CT>`, team part is missing in team switch lines
+fn parse_player(value: &str) -> Option<Player> {
+    // trailing `<...>` groups from the right, each with text preceding it
+    let mut groups: Vec<(&str, &str)> = Vec::new();
+    let mut rest = value;
+    while groups.len() < 3 {
+        let Some(stripped) = rest.strip_suffix('>') else {
+            break;
+        };
+        let open = stripped.rfind('<')?;
+        rest = &stripped[..open];
+        groups.push((&stripped[open + 1..], rest));
+    }
+
+    if let [(team, _), (steam_id, _), (user_id, name)] = groups[..] {
+        if let Ok(user_id) = user_id.parse::<i32>() {
+            return Some(Player {
+                name: String::from(name),
+                user_id,
+                steam_id: String::from(steam_id),
+                team: Some(Team::parse(team)),
+            });
+        }
+    }
+
+    let [(steam_id, _), (user_id, name), ..] = groups[..] else {
+        return None;
+    };
+    return Some(Player {
+        name: String::from(name),
+        user_id: user_id.parse().ok()?,
+        steam_id: String::from(steam_id),
+        team: None,
+    });
+}
+
+// Splits leading `"Name<..><..><..>"` from the rest of line
+fn split_player(text: &str) -> Option<(Player, &str)> {
+    let text = text.strip_prefix('"')?;
+    let end = text.find(">\"")?;
+    let player = parse_player(&text[..end + 1])?;
+    return Some((player, &text[end + 2..]));
+}
+
+// Skips ` [-100 200 30]` position that follows player when `mp_logdetail` is enabled
+fn skip_position(text: &str) -> &str {
+    let text = text.trim_start();
+    if text.starts_with('[') {
+        if let Some(end) = text.find(']') {
+            return text[end + 1..].trim_start();
+        }
+    }
+    return text;
+}
+
+// Value of `(key "value")` property
+fn property<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    let marker = format!("({key} \"");
+    let start = text.find(&marker)? + marker.len();
+    let end = text[start..].find('"')?;
+    return Some(&text[start..start + end]);
+}
+
+fn quoted(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start().strip_prefix('"')?;
+    let end = text.find('"')?;
+    return Some((&text[..end], &text[end + 1..]));
+}
+
+fn parse_world(rest: &str) -> Option<MatchEvent> {
+    let (trigger, rest) = quoted(rest.strip_prefix("triggered")?)?;
+    return match trigger {
+        "Round_Start" => Some(MatchEvent::RoundStart),
+        "Match_Start" => {
+            let (map, _) = quoted(rest.trim_start().strip_prefix("on")?)?;
+            Some(MatchEvent::MatchStart {
+                map: String::from(map),
+            })
+        }
+        _ => None,
+    };
+}
+
+// Team "CT" triggered "SFUI_Notice_CTs_Win" (CT "1") (T "0")
+fn parse_team(rest: &str) -> Option<MatchEvent> {
+    let (team, rest) = quoted(rest)?;
+    let (trigger, rest) = quoted(rest.trim_start().strip_prefix("triggered")?)?;
+    return Some(MatchEvent::RoundEnd {
+        winner: Team::parse(team),
+        trigger: String::from(trigger),
+        ct_score: property(rest, "CT")?.parse().ok()?,
+        t_score: property(rest, "T")?.parse().ok()?,
+    });
+}
+
+// Game Over: competitive mg_active de_dust2 score 13:7 after 35 min
+fn parse_game_over(rest: &str) -> Option<MatchEvent> {
+    let words: Vec<&str> = rest.split_whitespace().collect();
+    let score_at = words.iter().position(|w| *w == "score")?;
+    let (ct_score, t_score) = words.get(score_at + 1)?.split_once(':')?;
+    let minutes = words
+        .iter()
+        .position(|w| *w == "after")
+        .and_then(|at| words.get(at + 1)?.parse().ok())
+        .unwrap_or_default();
+    return Some(MatchEvent::MatchEnd {
+        mode: String::from(*words.first()?),
+        map: String::from(*words.get(score_at.checked_sub(1)?)?),
+        ct_score: ct_score.parse().ok()?,
+        t_score: t_score.parse().ok()?,
+        minutes,
+    });
+}
+
+fn parse_player_action(player: Player, rest: &str) -> Option<MatchEvent> {
+    let rest = skip_position(rest);
+
+    if let Some(rest) = rest.strip_prefix("killed ") {
+        let (victim, rest) = split_player(rest)?;
+        let rest = skip_position(rest);
+        let (weapon, rest) = quoted(rest.strip_prefix("with")?)?;
+        return Some(MatchEvent::Kill {
+            killer: player,
+            victim,
+            weapon: String::from(weapon),
+            headshot: rest.contains("headshot"),
+        });
+    }
+    if let Some(rest) = rest.strip_prefix("assisted killing ") {
+        let (victim, _) = split_player(rest)?;
+        return Some(MatchEvent::Assist {
+            assister: player,
+            victim,
+        });
+    }
+    if let Some(rest) = rest.strip_prefix("attacked ") {
+        let (victim, rest) = split_player(rest)?;
+        return Some(MatchEvent::Damage {
+            attacker: player,
+            victim,
+            damage: property(rest, "damage")?.parse().ok()?,
+            health: property(rest, "health")?.parse().ok()?,
+        });
+    }
+    if let Some(rest) = rest.strip_prefix("switched from team ") {
+        let (from, to) = rest.split_once(" to ")?;
+        let trim =
+            |team: &str| String::from(team.trim().trim_start_matches('<').trim_end_matches('>'));
+        return Some(MatchEvent::TeamSwitch {
+            player,
+            from: Team::parse(&trim(from)),
+            to: Team::parse(&trim(to)),
+        });
+    }
+    if let Some(rest) = rest.strip_prefix("triggered ") {
+        let (trigger, rest) = quoted(rest)?;
+        return match trigger {
+            "Planted_The_Bomb" => Some(MatchEvent::BombPlanted {
+                player,
+                site: String::from(rest.trim().trim_start_matches("at bombsite").trim()),
+            }),
+            "Defused_The_Bomb" => Some(MatchEvent::BombDefused { player }),
+            _ => None,
+        };
+    }
+    return None;
+}
+
+pub fn parse_line(line: &str) -> Option<MatchEvent> {
+    let body = strip_timestamp(line);
+
+    if let Some(rest) = body.strip_prefix("World ") {
+        return parse_world(rest);
+    }
+    if let Some(rest) = body.strip_prefix("Team ") {
+        return parse_team(rest);
+    }
+    if let Some(rest) = body.strip_prefix("Game Over:") {
+        return parse_game_over(rest);
+    }
+    let (player, rest) = split_player(body)?;
+    return parse_player_action(player, rest);
+}
+
+// Anything producing server log lines: live process, HTTP receiver or recorded log file
+pub trait LineSource {
+    // Lines received since last call
+    fn read_lines(&mut self) -> Vec<String>;
+}
+
+// Log saved earlier, returns all lines on first read
+#[cfg(test)]
+pub struct RecordedLog {
+    lines: Vec<String>,
+}
+
+#[cfg(test)]
+impl RecordedLog {
+    pub fn new(content: &str) -> RecordedLog {
+        return RecordedLog {
+            lines: content.lines().map(String::from).collect(),
+        };
+    }
+}
+
+#[cfg(test)]
+impl LineSource for RecordedLog {
+    fn read_lines(&mut self) -> Vec<String> {
+        return std::mem::take(&mut self.lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDED_MATCH: &str = include_str!("../testdata/cs2_match.log");
+
+    fn player(name: &str, user_id: i32, steam_id: &str, team: Option<Team>) -> Player {
+        return Player {
+            name: String::from(name),
+            user_id,
+            steam_id: String::from(steam_id),
+            team,
+        };
+    }
+
+    #[test]
+    fn strips_console_and_http_timestamps() {
+        assert_eq!(
+            strip_timestamp("L 10/19/2026 - 20:15:01: World triggered \"Round_Start\""),
+            "World triggered \"Round_Start\""
+        );
+        assert_eq!(
+            strip_timestamp("10/19/2026 - 20:15:01.123 - World triggered \"Round_Start\""),
+            "World triggered \"Round_Start\""
+        );
+        assert_eq!(
+            strip_timestamp("Server is hibernating"),
+            "Server is hibernating"
+        );
+    }
+
+    #[test]
+    fn parses_kill_with_position_and_headshot() {
+        let line = "L 10/19/2026 - 20:15:37: \"Sasha<4><[U:1:654321]><CT>\" [-1200 -700 -150] killed \"Rezan<3><BOT><TERRORIST>\" [-1384 -652 -147] with \"usp_silencer\" (headshot)";
+        assert_eq!(
+            parse_line(line),
+            Some(MatchEvent::Kill {
+                killer: player("Sasha", 4, "[U:1:654321]", Some(Team::CounterTerrorist)),
+                victim: player("Rezan", 3, "BOT", Some(Team::Terrorist)),
+                weapon: String::from("usp_silencer"),
+                headshot: true,
+            })
+        );
+
+        let line = "L 10/19/2026 - 20:16:10: \"Rezan<3><BOT><TERRORIST>\" [-900 -400 -150] killed \"Veritaris<2><[U:1:123456]><CT>\" [-1117 -789 -158] with \"ak47\"";
+        assert!(matches!(
+            parse_line(line),
+            Some(MatchEvent::Kill {
+                headshot: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_other_events() {
+        let events: Vec<MatchEvent> = RECORDED_MATCH.lines().filter_map(parse_line).collect();
+        assert_eq!(events.len(), RECORDED_MATCH.lines().count());
+
+        assert_eq!(
+            events[0],
+            MatchEvent::TeamSwitch {
+                player: player("Veritaris", 2, "[U:1:123456]", None),
+                from: Team::Unassigned,
+                to: Team::CounterTerrorist,
+            }
+        );
+        assert_eq!(
+            events[3],
+            MatchEvent::MatchStart {
+                map: String::from("de_dust2")
+            }
+        );
+        assert!(matches!(&events[6], MatchEvent::BombPlanted { site, .. } if site == "A"));
+        assert!(matches!(
+            events[7],
+            MatchEvent::Damage {
+                damage: 27,
+                health: 73,
+                ..
+            }
+        ));
+        assert!(
+            matches!(&events[11], MatchEvent::Assist { assister, .. } if assister.name == "Veritaris")
+        );
+        assert!(matches!(&events[12], MatchEvent::BombDefused { player } if player.user_id == 2));
+        assert_eq!(
+            events[13],
+            MatchEvent::RoundEnd {
+                winner: Team::CounterTerrorist,
+                trigger: String::from("SFUI_Notice_Bomb_Defused"),
+                ct_score: 1,
+                t_score: 0,
+            }
+        );
+        assert_eq!(
+            events[19],
+            MatchEvent::MatchEnd {
+                mode: String::from("competitive"),
+                map: String::from("de_dust2"),
+                ct_score: 1,
+                t_score: 1,
+                minutes: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_lines() {
+        assert_eq!(
+            parse_line("L 10/19/2026 - 20:15:01: server cvars start"),
+            None
+        );
+        assert_eq!(
+            parse_line("GC Connection established for server version 1234"),
+            None
+        );
+        assert_eq!(parse_line(""), None);
+    }
+
+    #[test]
+    fn recorded_log_is_read_once() {
+        let mut log = RecordedLog::new(RECORDED_MATCH);
+        assert_eq!(log.read_lines().len(), 20);
+        assert!(log.read_lines().is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+use crate::demos;
+use crate::scoreboard::Scoreboard;
+use crate::utils;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const MATCHES_DIR_NAME: &str = &"matches";
+const SUMMARY_FILE_NAME: &str = &"match.json";
+const PLAYERS_FILE_NAME: &str = &"players.csv";
+const ROUNDS_FILE_NAME: &str = &"rounds.csv";
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct RoundSummary {
+    pub number: u32,
+    pub winner: String,
+    pub reason: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct PlayerSummary {
+    // `[U:1:123]`, or `BOT name` for bots
+    pub steam_id: String,
+    pub name: String,
+    pub team: String,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub headshots: u32,
+    pub damage: u32,
+    pub adr: f32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct MatchSummary {
+    // UTC, `YYYY-MM-DD HH:MM:SS`
+    pub finished_at: String,
+    pub map: String,
+    pub mode: String,
+    pub minutes: u32,
+    pub ct_score: u32,
+    pub t_score: u32,
+    pub rounds: Vec<RoundSummary>,
+    pub players: Vec<PlayerSummary>,
+}
+
+impl MatchSummary {
+    pub fn from_scoreboard(scoreboard: &Scoreboard, finished_at: SystemTime) -> MatchSummary {
+        return MatchSummary {
+            finished_at: utils::format_utc(finished_at),
+            map: scoreboard.map.clone(),
+            mode: scoreboard.mode.clone(),
+            minutes: scoreboard.minutes,
+            ct_score: scoreboard.ct_score,
+            t_score: scoreboard.t_score,
+            rounds: scoreboard
+                .rounds
+                .iter()
+                .map(|round| RoundSummary {
+                    number: round.number,
+                    winner: String::from(round.winner.short_name()),
+                    reason: round.reason.clone(),
+                })
+                .collect(),
+            players: scoreboard
+                .players
+                .iter()
+                .map(|(key, stats)| PlayerSummary {
+                    steam_id: key.clone(),
+                    name: stats.name.clone(),
+                    team: String::from(stats.team.short_name()),
+                    kills: stats.kills,
+                    deaths: stats.deaths,
+                    assists: stats.assists,
+                    headshots: stats.headshots,
+                    damage: stats.damage,
+                    adr: scoreboard.adr(stats),
+                })
+                .collect(),
+        };
+    }
+}
+
+pub struct ExportedMatch {
+    pub dir: PathBuf,
+    pub summary: MatchSummary,
+}
+
+pub fn matches_dir() -> Option<PathBuf> {
+    return utils::project_dirs().map(|dirs| dirs.data_dir().join(MATCHES_DIR_NAME));
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    return String::from(value);
+}
+
+fn csv_row(fields: &[String]) -> String {
+    let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    return row.join(",") + "\n";
+}
+
+fn players_csv(summary: &MatchSummary) -> String {
+    let mut csv = csv_row(
+        &[
+            "steam_id",
+            "name",
+            "team",
+            "kills",
+            "deaths",
+            "assists",
+            "headshots",
+            "damage",
+            "adr",
+        ]
+        .map(String::from),
+    );
+    for p in &summary.players {
+        csv += &csv_row(&[
+            p.steam_id.clone(),
+            p.name.clone(),
+            p.team.clone(),
+            p.kills.to_string(),
+            p.deaths.to_string(),
+            p.assists.to_string(),
+            p.headshots.to_string(),
+            p.damage.to_string(),
+            format!("{:.1}", p.adr),
+        ]);
+    }
+    return csv;
+}
+
+fn rounds_csv(summary: &MatchSummary) -> String {
+    let mut csv = csv_row(&["round", "winner", "reason"].map(String::from));
+    for round in &summary.rounds {
+        csv += &csv_row(&[
+            round.number.to_string(),
+            round.winner.clone(),
+            round.reason.clone(),
+        ]);
+    }
+    return csv;
+}
+
+// Writes summary into its own `<date>_<map>` directory inside `dir`,
+// workshop maps like `workshop/123/de_x` must not create nested directories
+pub fn export(dir: &Path, summary: &MatchSummary) -> io::Result<PathBuf> {
+    let name = format!(
+        "{}_{}",
+        summary.finished_at.replace([' ', ':'], "-"),
+        demos::sanitize(&summary.map)
+    );
+    let match_dir = dir.join(name);
+    fs::create_dir_all(&match_dir)?;
+
+    let json = serde_json::to_string_pretty(summary)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fs::write(match_dir.join(SUMMARY_FILE_NAME), json)?;
+    fs::write(match_dir.join(PLAYERS_FILE_NAME), players_csv(summary))?;
+    fs::write(match_dir.join(ROUNDS_FILE_NAME), rounds_csv(summary))?;
+    return Ok(match_dir);
+}
+
+// Previously exported matches, newest first
+pub fn list(dir: &Path) -> Vec<ExportedMatch> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut matches: Vec<ExportedMatch> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let content = fs::read_to_string(e.path().join(SUMMARY_FILE_NAME)).ok()?;
+            return Some(ExportedMatch {
+                dir: e.path(),
+                summary: serde_json::from_str(&content).ok()?,
+            });
+        })
+        .collect();
+    matches.sort_by(|a, b| b.summary.finished_at.cmp(&a.summary.finished_at));
+    return matches;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("match_export_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        return dir;
+    }
+
+    fn summary(finished_at: &str, map: &str) -> MatchSummary {
+        return MatchSummary {
+            finished_at: String::from(finished_at),
+            map: String::from(map),
+            mode: String::from("competitive"),
+            minutes: 41,
+            ct_score: 13,
+            t_score: 11,
+            rounds: vec![
+                RoundSummary {
+                    number: 1,
+                    winner: String::from("CT"),
+                    reason: String::from("Bomb defused"),
+                },
+                RoundSummary {
+                    number: 2,
+                    winner: String::from("T"),
+                    reason: String::from("Elimination"),
+                },
+            ],
+            players: vec![
+                PlayerSummary {
+                    steam_id: String::from("[U:1:123456]"),
+                    name: String::from("Sasha, \"the Pro\""),
+                    team: String::from("CT"),
+                    kills: 21,
+                    deaths: 14,
+                    assists: 5,
+                    headshots: 9,
+                    damage: 2104,
+                    adr: 87.666,
+                },
+                PlayerSummary {
+                    steam_id: String::from("BOT Rezan"),
+                    name: String::from("Rezan"),
+                    team: String::from("T"),
+                    kills: 14,
+                    deaths: 21,
+                    assists: 2,
+                    headshots: 3,
+                    damage: 1530,
+                    adr: 63.75,
+                },
+            ],
+        };
+    }
+
+    #[test]
+    fn quotes_csv_fields() {
+        assert_eq!(csv_field("plain name"), "plain name");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("two\r\nlines"), "\"two\r\nlines\"");
+    }
+
+    #[test]
+    fn writes_players_and_rounds_csv() {
+        let summary = summary("2026-10-19 20:41:07", "de_dust2");
+        assert_eq!(
+            players_csv(&summary),
+            "steam_id,name,team,kills,deaths,assists,headshots,damage,adr\n\
+             [U:1:123456],\"Sasha, \"\"the Pro\"\"\",CT,21,14,5,9,2104,87.7\n\
+             BOT Rezan,Rezan,T,14,21,2,3,1530,63.8\n"
+        );
+        assert_eq!(
+            rounds_csv(&summary),
+            "round,winner,reason\n1,CT,Bomb defused\n2,T,Elimination\n"
+        );
+    }
+
+    #[test]
+    fn exported_matches_are_listed() {
+        let dir = temp_dir("round_trip");
+        let first = export(&dir, &summary("2026-10-19 20:41:07", "de_dust2")).unwrap();
+        let workshop = export(
+            &dir,
+            &summary("2026-10-20 18:02:55", "workshop/3070284539/de_x"),
+        )
+        .unwrap();
+
+        assert_eq!(first, dir.join("2026-10-19-20-41-07_de_dust2"));
+        assert_eq!(workshop.parent(), Some(dir.as_path()));
+        assert!(workshop.join(PLAYERS_FILE_NAME).exists());
+        assert!(workshop.join(ROUNDS_FILE_NAME).exists());
+
+        let listed = list(&dir);
+        let maps: Vec<&str> = listed.iter().map(|m| m.summary.map.as_str()).collect();
+        assert_eq!(maps, ["workshop/3070284539/de_x", "de_dust2"]);
+        assert_eq!(listed[1].dir, first);
+        assert_eq!(listed[1].summary.players[0].name, "Sasha, \"the Pro\"");
+        assert_eq!(listed[1].summary.rounds.len(), 2);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::match_events::{MatchEvent, Player, Team};
+use std::collections::HashMap;
+use std::time::Instant;
+
+// CS2 prints `---------players--------`, CS:GO style servers print `# userid name uniqueid ...`
+const STATUS_TABLE_MARKERS: &[&str] = &["---------players--------", "# userid"];
+const STATUS_TABLE_END: &str = &"#end";
+// slot of connection that is not a player yet
+const CHALLENGE_USER_ID: i32 = 65535;
+// give up on table that never ends, e.g. output was cut
+const MAX_STATUS_ROWS: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusPlayer {
+    pub user_id: i32,
+    pub name: String,
+    // only CS:GO style status has it
+    pub steam_id: Option<String>,
+    // `MM:SS` or `HH:MM:SS` since connect
+    pub connected: String,
+    pub ping: u32,
+    pub loss: u32,
+    pub state: String,
+    pub bot: bool,
+}
+
+// `    2    00:20   35    0     active 786432 192.168.1.5:27005 'Name'`
+fn parse_cs2_row(line: &str) -> Option<StatusPlayer> {
+    let name_start = line.find('\'')?;
+    let name = line[name_start + 1..].trim_end().strip_suffix('\'')?;
+    let fields: Vec<&str> = line[..name_start].split_whitespace().collect();
+    let [user_id, connected, ping, loss, state, ..] = fields[..] else {
+        return None;
+    };
+
+    let user_id: i32 = user_id.parse().ok()?;
+    if user_id == CHALLENGE_USER_ID {
+        return None;
+    }
+    return Some(StatusPlayer {
+        user_id,
+        name: String::from(name),
+        steam_id: None,
+        connected: String::from(connected),
+        ping: ping.parse().ok()?,
+        loss: loss.parse().ok()?,
+        state: String::from(state),
+        bot: connected == "BOT",
+    });
+}
+
+// `# 2 1 "Name" STEAM_1:0:123 00:20 35 0 active 786432 1.2.3.4:27005` or `# 3 "Bot" BOT active 64`
+fn parse_csgo_row(line: &str) -> Option<StatusPlayer> {
+    let line = line.strip_prefix('#')?;
+    let name_start = line.find('"')?;
+    let name_end = line.rfind('"')?;
+    if name_end <= name_start {
+        return None;
+    }
+    let user_id: i32 = line[..name_start].split_whitespace().next()?.parse().ok()?;
+    let name = &line[name_start + 1..name_end];
+    let fields: Vec<&str> = line[name_end + 1..].split_whitespace().collect();
+
+    if let ["BOT", state, ..] = fields[..] {
+        return Some(StatusPlayer {
+            user_id,
+            name: String::from(name),
+            steam_id: None,
+            connected: String::from("BOT"),
+            ping: 0,
+            loss: 0,
+            state: String::from(state),
+            bot: true,
+        });
+    }
+    let [steam_id, connected, ping, loss, state, ..] = fields[..] else {
+        return None;
+    };
+    return Some(StatusPlayer {
+        user_id,
+        name: String::from(name),
+        steam_id: Some(String::from(steam_id)),
+        connected: String::from(connected),
+        ping: ping.parse().ok()?,
+        loss: loss.parse().ok()?,
+        state: String::from(state),
+        bot: false,
+    });
+}
+
+pub fn parse_status_row(line: &str) -> Option<StatusPlayer> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return parse_csgo_row(line);
+    }
+    return parse_cs2_row(line);
+}
+
+// Collects players table of `status` command from console output line by line
+#[derive(Default)]
+pub struct StatusParser {
+    // Some while inside of players table
+    rows: Option<Vec<StatusPlayer>>,
+}
+
+impl StatusParser {
+    // Returns all players once table ends
+    pub fn feed(&mut self, line: &str) -> Option<Vec<StatusPlayer>> {
+        let line = line.trim();
+        if STATUS_TABLE_MARKERS.iter().any(|m| line.starts_with(m)) {
+            self.rows = Some(Vec::new());
+            return None;
+        }
+        let rows = self.rows.as_mut()?;
+        if line == STATUS_TABLE_END {
+            return self.rows.take();
+        }
+        if let Some(row) = parse_status_row(line) {
+            rows.push(row);
+        }
+        if rows.len() > MAX_STATUS_ROWS {
+            self.rows = None;
+        }
+        return None;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RosterPlayer {
+    pub status: StatusPlayer,
+    // empty until player shows up in logs, CS2 `status` has no SteamID
+    pub steam_id: String,
+    pub team: Team,
+}
+
+// Connected players from `status`, completed with SteamID and team seen in logs
+#[derive(Default)]
+pub struct PlayerRoster {
+    pub players: Vec<RosterPlayer>,
+    pub updated_at: Option<Instant>,
+    parser: StatusParser,
+    // last known log identity per user id
+    known: HashMap<i32, Player>,
+}
+
+impl PlayerRoster {
+    pub fn observe(&mut self, events: &[MatchEvent]) {
+        for event in events {
+            for player in event.players() {
+                let known = self
+                    .known
+                    .entry(player.user_id)
+                    .or_insert_with(|| player.clone());
+                known.name = player.name.clone();
+                known.steam_id = player.steam_id.clone();
+                if player.team.is_some() {
+                    known.team = player.team;
+                }
+            }
+            if let MatchEvent::TeamSwitch { player, to, .. } = event {
+                if let Some(known) = self.known.get_mut(&player.user_id) {
+                    known.team = Some(*to);
+                }
+            }
+        }
+        for roster_player in &mut self.players {
+            if let Some(known) = self.known.get(&roster_player.status.user_id) {
+                roster_player.team = known.team.unwrap_or(Team::Unassigned);
+            }
+        }
+    }
+
+    // Returns true when new `status` table was read
+    pub fn feed(&mut self, line: &str) -> bool {
+        let Some(rows) = self.parser.feed(line) else {
+            return false;
+        };
+        self.players = rows
+            .into_iter()
+            .map(|status| {
+                let known = self.known.get(&status.user_id);
+                return RosterPlayer {
+                    steam_id: status
+                        .steam_id
+                        .clone()
+                        .or_else(|| known.map(|k| k.steam_id.clone()))
+                        .unwrap_or_default(),
+                    team: known.and_then(|k| k.team).unwrap_or(Team::Unassigned),
+                    status,
+                };
+            })
+            .collect();
+        self.updated_at = Some(Instant::now());
+        return true;
+    }
+}
+
+// Vanilla CS2 has no server commands to move or mute single player,
+// those use CS2-SimpleAdmin plugin commands
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerAction {
+    Kick,
+    Ban { minutes: u32 },
+    Move(Team),
+    Mute,
+}
+
+impl PlayerAction {
+    pub fn label(&self) -> String {
+        return match self {
+            PlayerAction::Kick => String::from("Kick"),
+            PlayerAction::Ban { minutes: 0 } => String::from("Ban permanently"),
+            PlayerAction::Ban { minutes } => format!("Ban for {minutes} min"),
+            PlayerAction::Move(team) => format!("Move to {}", team.short_name()),
+            PlayerAction::Mute => String::from("Mute"),
+        };
+    }
+
+    pub fn needs_plugin(&self) -> bool {
+        return matches!(self, PlayerAction::Move(_) | PlayerAction::Mute);
+    }
+
+    pub fn commands(&self, user_id: i32) -> Vec<String> {
+        return match self {
+            PlayerAction::Kick => vec![format!("kickid {user_id}")],
+            // ban list only blocks reconnects, player is kicked separately
+            PlayerAction::Ban { minutes } => vec![
+                format!("banid {minutes} {user_id}"),
+                format!("kickid {user_id}"),
+            ],
+            PlayerAction::Move(team) => {
+                let team = match team {
+                    Team::CounterTerrorist => "ct",
+                    Team::Terrorist => "t",
+                    Team::Spectator | Team::Unassigned => "spec",
+                };
+                vec![format!("css_team #{user_id} {team}")]
+            }
+            PlayerAction::Mute => vec![format!("css_mute #{user_id}")],
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `status` output of CS2 dedicated server with one player, bots and a connecting client
+    const CS2_STATUS: &str = "Server:  Running [0.0.0.0:27015]
+Client:  Disconnected
+Source TV:  Running [0.0.0.0:27020]
+---------server info---------
+  hostname  : Prestarter LAN
+  spawn     : 1
+  version   : 1.40.3.5/14035 10/19/2026 secure
+---------players--------
+  id     time ping loss      state   rate adr name
+65535 [NoChan]    0    0 challenging      0unknown ''
+    2    04:18   24    0     active 786432 192.168.1.5:27005 'Sasha the \"Pro\"'
+    3      BOT    0    0     active      0 'Rezan'
+    4    00:02   61    3     active 196608 192.168.1.7:27005 'O'Brien'
+#end
+";
+
+    fn feed_all(parser: &mut StatusParser, output: &str) -> Option<Vec<StatusPlayer>> {
+        let mut table = None;
+        for line in output.lines() {
+            if let Some(rows) = parser.feed(line) {
+                table = Some(rows);
+            }
+        }
+        return table;
+    }
+
+    #[test]
+    fn parses_cs2_rows() {
+        assert_eq!(
+            parse_status_row(
+                "    2    04:18   24    0     active 786432 192.168.1.5:27005 'Sasha the \"Pro\"'"
+            ),
+            Some(StatusPlayer {
+                user_id: 2,
+                name: String::from("Sasha the \"Pro\""),
+                steam_id: None,
+                connected: String::from("04:18"),
+                ping: 24,
+                loss: 0,
+                state: String::from("active"),
+                bot: false,
+            })
+        );
+
+        let bot = parse_status_row("    3      BOT    0    0     active      0 'Rezan'").unwrap();
+        assert!(bot.bot);
+        assert_eq!(bot.name, "Rezan");
+
+        assert_eq!(
+            parse_status_row("65535 [NoChan]    0    0 challenging      0unknown ''"),
+            None
+        );
+        assert_eq!(
+            parse_status_row("  id     time ping loss      state   rate adr name"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_csgo_rows() {
+        let player = parse_status_row(
+            "# 2 1 \"Sasha\" STEAM_1:0:123 00:20 35 0 active 786432 1.2.3.4:27005",
+        )
+        .unwrap();
+        assert_eq!(player.steam_id.as_deref(), Some("STEAM_1:0:123"));
+        assert_eq!((player.user_id, player.ping), (2, 35));
+
+        let bot = parse_status_row("# 3 \"Rezan\" BOT active 64").unwrap();
+        assert!(bot.bot);
+        assert_eq!(bot.name, "Rezan");
+
+        assert_eq!(
+            parse_status_row("# userid name uniqueid connected ping loss state rate adr"),
+            None
+        );
+    }
+
+    #[test]
+    fn collects_table_until_end() {
+        let mut parser = StatusParser::default();
+        let players = feed_all(&mut parser, CS2_STATUS).unwrap();
+
+        let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Sasha the \"Pro\"", "Rezan", "O'Brien"]);
+        assert_eq!(players[2].loss, 3);
+    }
+
+    #[test]
+    fn cut_table_is_dropped() {
+        let mut parser = StatusParser::default();
+        let cut: String = CS2_STATUS
+            .lines()
+            .take_while(|line| !line.starts_with("    4"))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        assert_eq!(feed_all(&mut parser, &cut), None);
+
+        // next `status` starts over instead of appending to unfinished table
+        let players = feed_all(&mut parser, CS2_STATUS).unwrap();
+        assert_eq!(players.len(), 3);
+    }
+
+    #[test]
+    fn gives_up_on_endless_table() {
+        let mut parser = StatusParser::default();
+        parser.feed("---------players--------");
+        for user_id in 0..=MAX_STATUS_ROWS {
+            parser.feed(&format!(
+                "{user_id} 00:01 5 0 active 786432 1.2.3.4:27005 'Player'"
+            ));
+        }
+        assert_eq!(parser.feed(STATUS_TABLE_END), None);
+    }
+}
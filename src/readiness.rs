@@ -0,0 +1,184 @@
+use std::fmt;
+
+const LOADING_MARKERS: &[&str] = &["Host activate:", "Changelevel", "Host_NewGame"];
+// after map change GC does not reconnect, `Started map` is logged after every map load
+const READY_MARKERS: &[&str] = &[
+    "GC Connection established",
+    "Server is ready",
+    "Started map \"",
+    "entered the game",
+];
+const HIBERNATING_MARKERS: &[&str] = &["Server is hibernating"];
+const WAKE_MARKERS: &[&str] = &["Server waking up from hibernation"];
+const BIND_FAILURE_MARKERS: &[&str] = &[
+    "Couldn't allocate any server IP port",
+    "unable to bind socket",
+    "Failed to open socket",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServerState {
+    Starting,
+    LoadingMap,
+    Ready,
+    Hibernating,
+    Stopped,
+}
+
+impl fmt::Display for ServerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ServerState::Starting => "Starting",
+            ServerState::LoadingMap => "Loading map",
+            ServerState::Ready => "Ready",
+            ServerState::Hibernating => "Hibernating",
+            ServerState::Stopped => "Stopped",
+        };
+        return write!(f, "{name}");
+    }
+}
+
+impl ServerState {
+    // Players can join only after map is loaded
+    pub fn is_joinable(&self) -> bool {
+        return matches!(self, ServerState::Ready | ServerState::Hibernating);
+    }
+}
+
+fn contains_any(line: &str, markers: &[&str]) -> bool {
+    return markers.iter().any(|marker| line.contains(marker));
+}
+
+// Follows server console output to tell when it is ready to accept players
+pub struct Readiness {
+    pub state: ServerState,
+    // reason of unexpected stop, e.g. port already in use
+    pub failure: Option<String>,
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self {
+            state: ServerState::Stopped,
+            failure: None,
+        }
+    }
+}
+
+impl Readiness {
+    pub fn started() -> Readiness {
+        return Readiness {
+            state: ServerState::Starting,
+            failure: None,
+        };
+    }
+
+    pub fn feed(&mut self, line: &str) {
+        if self.state == ServerState::Stopped {
+            return;
+        }
+
+        if contains_any(line, BIND_FAILURE_MARKERS) {
+            self.state = ServerState::Stopped;
+            self.failure = Some(String::from(line.trim()));
+        } else if contains_any(line, HIBERNATING_MARKERS) {
+            self.state = ServerState::Hibernating;
+        } else if contains_any(line, WAKE_MARKERS) {
+            self.state = ServerState::Ready;
+        } else if contains_any(line, LOADING_MARKERS) {
+            self.state = ServerState::LoadingMap;
+        } else if self.state != ServerState::Hibernating && contains_any(line, READY_MARKERS) {
+            self.state = ServerState::Ready;
+        }
+    }
+
+    pub fn stopped(&mut self) {
+        self.state = ServerState::Stopped;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(readiness: &mut Readiness, lines: &[&str]) {
+        for line in lines {
+            readiness.feed(line);
+        }
+    }
+
+    #[test]
+    fn becomes_ready_after_start() {
+        let mut readiness = Readiness::started();
+        assert_eq!(readiness.state, ServerState::Starting);
+        readiness.feed("Host activate: Loading (de_dust2)");
+        assert_eq!(readiness.state, ServerState::LoadingMap);
+        readiness.feed("GC Connection established for server version 2000623");
+        assert_eq!(readiness.state, ServerState::Ready);
+        assert!(readiness.state.is_joinable());
+    }
+
+    #[test]
+    fn follows_hibernation() {
+        let mut readiness = Readiness::started();
+        feed_all(
+            &mut readiness,
+            &["Host activate: Loading (de_dust2)", "Server is ready"],
+        );
+        readiness.feed("Server is hibernating");
+        assert_eq!(readiness.state, ServerState::Hibernating);
+        // hibernating server still accepts players
+        assert!(readiness.state.is_joinable());
+        readiness.feed("L 10/19/2026 - 20:15:01: \"Sasha<2><[U:1:1]><>\" entered the game");
+        assert_eq!(readiness.state, ServerState::Hibernating);
+        readiness.feed("Server waking up from hibernation");
+        assert_eq!(readiness.state, ServerState::Ready);
+    }
+
+    #[test]
+    fn map_change_on_empty_server_comes_back_ready() {
+        let mut readiness = Readiness::started();
+        feed_all(
+            &mut readiness,
+            &["Host activate: Loading (de_dust2)", "Server is ready"],
+        );
+        readiness.feed("Host activate: Changelevel (de_mirage)");
+        assert_eq!(readiness.state, ServerState::LoadingMap);
+        assert!(!readiness.state.is_joinable());
+        readiness.feed("L 10/19/2026 - 20:20:11: Started map \"de_mirage\" (CRC \"-1416375487\")");
+        assert_eq!(readiness.state, ServerState::Ready);
+    }
+
+    #[test]
+    fn bind_failure_stops() {
+        let mut readiness = Readiness::started();
+        readiness.feed("  WARNING: Couldn't allocate any server IP port  ");
+        assert_eq!(readiness.state, ServerState::Stopped);
+        assert_eq!(
+            readiness.failure.as_deref(),
+            Some("WARNING: Couldn't allocate any server IP port")
+        );
+
+        // process output after failure does not revive it
+        feed_all(
+            &mut readiness,
+            &["Host activate: Loading (de_dust2)", "Server is ready"],
+        );
+        assert_eq!(readiness.state, ServerState::Stopped);
+    }
+
+    #[test]
+    fn stopped_ignores_lines() {
+        let mut readiness = Readiness::default();
+        readiness.feed("Server is ready");
+        assert_eq!(readiness.state, ServerState::Stopped);
+        assert_eq!(readiness.failure, None);
+
+        let mut readiness = Readiness::started();
+        readiness.feed("Server is ready");
+        readiness.stopped();
+        readiness.feed("unable to bind socket");
+        assert_eq!(readiness.state, ServerState::Stopped);
+        assert_eq!(readiness.failure, None);
+    }
+}
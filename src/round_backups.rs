@@ -0,0 +1,153 @@
+use crate::keyvalues::{self, KeyValues};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const BACKUP_PREFIX: &str = &"backup_round";
+const BACKUP_EXT: &str = &"txt";
+const SCORE_SECTIONS: &[&str] = &["FirstHalfScore", "SecondHalfScore", "OvertimeScore"];
+
+#[derive(Default, Clone)]
+pub struct BackupInfo {
+    pub map: String,
+    pub round: u32,
+    // as written by the server, e.g. `2024/01/31 20:15:33`
+    pub saved_at: String,
+    pub team1_score: u32,
+    pub team2_score: u32,
+}
+
+pub struct RoundBackup {
+    pub path: PathBuf,
+    pub name: String,
+    pub modified: SystemTime,
+    pub info: Option<BackupInfo>,
+}
+
+fn section_score(section: Option<&KeyValues>, team: &str) -> u32 {
+    return section.and_then(|s| s.get_parsed(team)).unwrap_or(0);
+}
+
+// `"SaveFile" { "date" .. "time" .. "map" .. "round" .. "FirstHalfScore" { "team1" .. } .. }`
+pub fn parse(content: &str) -> Option<BackupInfo> {
+    let root = keyvalues::parse(content)?;
+    let save = root.get_section("SaveFile")?;
+
+    let mut info = BackupInfo {
+        map: String::from(save.get_str("map").unwrap_or("")),
+        round: save.get_parsed("round").unwrap_or(0),
+        saved_at: format!(
+            "{} {}",
+            save.get_str("date").unwrap_or(""),
+            save.get_str("time").unwrap_or("")
+        )
+        .trim()
+        .to_string(),
+        ..BackupInfo::default()
+    };
+    // total score is split between halves and overtime
+    for name in SCORE_SECTIONS {
+        let section = save.get_section(name);
+        info.team1_score += section_score(section, "team1");
+        info.team2_score += section_score(section, "team2");
+    }
+    return Some(info);
+}
+
+// `backup_roundNN.txt` files in given directory, newest first
+pub fn list(dir: &Path) -> Vec<RoundBackup> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<RoundBackup> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            let path = e.path();
+            if !name.starts_with(BACKUP_PREFIX)
+                || !path.extension().is_some_and(|ext| ext == BACKUP_EXT)
+            {
+                return None;
+            }
+            return Some(RoundBackup {
+                modified: e.metadata().ok()?.modified().ok()?,
+                info: fs::read_to_string(&path).ok().and_then(|c| parse(&c)),
+                name,
+                path,
+            });
+        })
+        .collect();
+    backups.sort_by_key(|b| Reverse(b.modified));
+    return backups;
+}
+
+// Server looks for backups in its content dir, so only file name is passed
+pub fn restore_command(backup: &RoundBackup) -> String {
+    return format!("mp_backup_restore_load_file \"{}\"", backup.name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // overtime round of a match played with `mp_maxrounds 4`
+    const BACKUP: &str = include_str!("../testdata/backup_round05.txt");
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("round_backups_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    #[test]
+    fn parses_backup() {
+        let info = parse(BACKUP).unwrap();
+        assert_eq!(info.map, "de_dust2");
+        assert_eq!(info.round, 5);
+        assert_eq!(info.saved_at, "2026/10/19 20:41:07");
+        // halves and overtime added up
+        assert_eq!((info.team1_score, info.team2_score), (3, 2));
+    }
+
+    #[test]
+    fn missing_sections_count_as_zero() {
+        let info = parse(
+            "\"SaveFile\" { \"map\" \"de_nuke\" \"round\" \"2\" \
+             \"FirstHalfScore\" { \"team1\" \"1\" \"team2\" \"0\" } }",
+        )
+        .unwrap();
+        assert_eq!((info.team1_score, info.team2_score), (1, 0));
+        assert_eq!(info.saved_at, "");
+        assert!(parse("\"Other\" { }").is_none());
+    }
+
+    #[test]
+    fn lists_only_round_backups() {
+        let dir = temp_dir("list");
+        for name in [
+            "backup_round05.txt",
+            "backup_round06.txt",
+            "backup_round07.bak",
+            "round05.txt",
+            "motd.txt",
+        ] {
+            fs::write(dir.join(name), BACKUP).unwrap();
+        }
+        fs::write(dir.join("backup_round06.txt"), "\"SaveFile\" {").unwrap();
+
+        let mut backups = list(&dir);
+        backups.sort_by(|a, b| a.name.cmp(&b.name));
+        let names: Vec<&str> = backups.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["backup_round05.txt", "backup_round06.txt"]);
+        assert_eq!(backups[0].info.as_ref().unwrap().round, 5);
+        // unreadable backup is still listed so it can be restored
+        assert!(backups[1].info.is_none());
+        assert_eq!(
+            restore_command(&backups[0]),
+            "mp_backup_restore_load_file \"backup_round05.txt\""
+        );
+    }
+}
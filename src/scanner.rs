@@ -0,0 +1,176 @@
+use crate::game::{self, GameVersion, InstallCheck};
+use crate::steam::{self, AppManifest, DiscoveryError, ReadyState, SteamApp};
+use crate::utils;
+use linked_hash_map::LinkedHashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+const CACHE_FILE_NAME: &str = &"last_scan.json";
+
+#[derive(Clone, PartialEq)]
+pub struct ScanRequest {
+    pub app_id: u32,
+    // custom install directory, empty to use Steam detection
+    pub game_path_override: String,
+}
+
+pub struct ScanOutcome {
+    pub state: ReadyState,
+    pub game_path: String,
+    pub apps: LinkedHashMap<u32, SteamApp>,
+    pub maps: Vec<String>,
+    pub install_checks: Vec<InstallCheck>,
+    pub version: Option<GameVersion>,
+    pub manifest: Option<AppManifest>,
+    pub problems: Vec<DiscoveryError>,
+}
+
+pub enum ScanEvent {
+    Progress(String),
+    // boxed, outcome is much bigger than progress messages
+    Finished(Box<ScanOutcome>),
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct CachedScan {
+    app_id: u32,
+    game_path_override: String,
+    game_path: String,
+    apps: Vec<SteamApp>,
+    maps: Vec<String>,
+}
+
+pub fn start(request: ScanRequest) -> Receiver<ScanEvent> {
+    let (sender, receiver) = mpsc::channel::<ScanEvent>();
+
+    thread::spawn(move || {
+        let outcome = scan(&request, &|message: String| {
+            let _ = sender.send(ScanEvent::Progress(message));
+        });
+
+        if outcome.state == ReadyState::Ready {
+            if let Err(err) = save_cache(&request, &outcome) {
+                log::warn!("Unable to save scan cache: {err}");
+            }
+        }
+        let _ = sender.send(ScanEvent::Finished(Box::new(outcome)));
+    });
+
+    return receiver;
+}
+
+// Resolves game path from override or Steam app, verifies install and lists maps
+pub fn scan(request: &ScanRequest, progress: &dyn Fn(String)) -> ScanOutcome {
+    let mut discovery = steam::discover(progress);
+    let mut outcome = ScanOutcome {
+        state: ReadyState::NoGame,
+        game_path: String::new(),
+        apps: LinkedHashMap::new(),
+        maps: Vec::new(),
+        install_checks: Vec::new(),
+        version: None,
+        manifest: None,
+        problems: Vec::new(),
+    };
+
+    if !request.game_path_override.is_empty() {
+        if Path::new(&request.game_path_override).is_dir() {
+            outcome.game_path = request.game_path_override.clone();
+        } else {
+            discovery
+                .problems
+                .push(DiscoveryError::MissingGameDir(PathBuf::from(
+                    &request.game_path_override,
+                )));
+        }
+    } else if !discovery.steam_found {
+        outcome.state = ReadyState::NoSteam;
+    } else {
+        match discovery.app_dir(&request.app_id) {
+            Ok(res) => outcome.game_path = res,
+            Err(err) => discovery.problems.push(err),
+        }
+    }
+
+    let definition = game::definition_for_app(request.app_id);
+    if definition.is_none() {
+        discovery
+            .problems
+            .push(DiscoveryError::UnsupportedGame(request.app_id));
+    }
+
+    if let (Some(definition), false) = (definition, outcome.game_path.is_empty()) {
+        progress(format!("Checking install at {}", outcome.game_path));
+        outcome.install_checks = game::check_install(definition, &outcome.game_path);
+
+        if outcome.install_checks.iter().all(|c| c.found) {
+            progress(String::from("Scanning maps"));
+            outcome.maps =
+                game::get_available_maps(definition, &outcome.game_path).unwrap_or_default();
+            outcome.version = GameVersion::read(definition, &outcome.game_path);
+            outcome.manifest = AppManifest::for_install_dir(&outcome.game_path, &request.app_id);
+            outcome.state = ReadyState::Ready;
+        } else {
+            discovery
+                .problems
+                .push(DiscoveryError::IncompleteInstall(PathBuf::from(
+                    &outcome.game_path,
+                )));
+        }
+    }
+
+    for problem in &discovery.problems {
+        log::warn!("{problem}");
+    }
+
+    outcome.apps = discovery.apps;
+    outcome.problems = discovery.problems;
+    return outcome;
+}
+
+fn cache_path() -> Option<PathBuf> {
+    return utils::project_dirs().map(|dirs| dirs.cache_dir().join(CACHE_FILE_NAME));
+}
+
+// Last successful scan for the same request, used until background scan finishes
+pub fn load_cache(request: &ScanRequest) -> Option<ScanOutcome> {
+    let content = fs::read_to_string(cache_path()?).ok()?;
+    let cached: CachedScan = serde_json::from_str(&content).ok()?;
+
+    if cached.app_id != request.app_id || cached.game_path_override != request.game_path_override {
+        return None;
+    }
+
+    return Some(ScanOutcome {
+        state: ReadyState::Ready,
+        game_path: cached.game_path,
+        apps: LinkedHashMap::from_iter(cached.apps.into_iter().map(|a| (a.app_id, a))),
+        maps: cached.maps,
+        install_checks: Vec::new(),
+        version: None,
+        manifest: None,
+        problems: Vec::new(),
+    });
+}
+
+fn save_cache(request: &ScanRequest, outcome: &ScanOutcome) -> io::Result<()> {
+    let path = match cache_path() {
+        Some(res) => res,
+        None => return Ok(()),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let cached = CachedScan {
+        app_id: request.app_id,
+        game_path_override: request.game_path_override.clone(),
+        game_path: outcome.game_path.clone(),
+        apps: outcome.apps.values().cloned().collect(),
+        maps: outcome.maps.clone(),
+    };
+    return fs::write(path, serde_json::to_string(&cached)?);
+}
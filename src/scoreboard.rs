@@ -0,0 +1,258 @@
+use crate::match_events::{self, LineSource, MatchEvent, Player, Team};
+use linked_hash_map::LinkedHashMap;
+use std::collections::HashMap;
+
+const FULL_HEALTH: u32 = 100;
+
+#[derive(Clone)]
+pub struct PlayerStats {
+    pub name: String,
+    pub team: Team,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub headshots: u32,
+    pub damage: u32,
+}
+
+#[derive(Clone)]
+pub struct RoundResult {
+    pub number: u32,
+    pub winner: Team,
+    pub reason: String,
+    pub bomb_planted: bool,
+    pub bomb_defused: bool,
+}
+
+// Live state of current match built from log events
+#[derive(Default, Clone)]
+pub struct Scoreboard {
+    pub map: String,
+    // known only after match ends, e.g. `competitive`
+    pub mode: String,
+    pub minutes: u32,
+    pub ct_score: u32,
+    pub t_score: u32,
+    // keyed by `Player::key`, in order of appearance
+    pub players: LinkedHashMap<String, PlayerStats>,
+    pub rounds: Vec<RoundResult>,
+    pub finished: bool,
+    // health left per player in current round, caps damage like in-game ADR does
+    health: HashMap<String, u32>,
+    bomb_planted: bool,
+    bomb_defused: bool,
+    reported: bool,
+}
+
+impl Scoreboard {
+    fn stats(&mut self, player: &Player) -> &mut PlayerStats {
+        let stats = self
+            .players
+            .entry(player.key())
+            .or_insert_with(|| PlayerStats {
+                name: player.name.clone(),
+                team: player.team.unwrap_or(Team::Unassigned),
+                kills: 0,
+                deaths: 0,
+                assists: 0,
+                headshots: 0,
+                damage: 0,
+            });
+        stats.name = player.name.clone();
+        if let Some(team) = player.team {
+            stats.team = team;
+        }
+        return stats;
+    }
+
+    pub fn apply(&mut self, event: &MatchEvent) {
+        match event {
+            MatchEvent::MatchStart { map } => {
+                // warmup ends with Match_Start, stats collected so far don't count
+                *self = Scoreboard {
+                    map: map.clone(),
+                    ..Scoreboard::default()
+                };
+            }
+            MatchEvent::RoundStart => {
+                self.health.clear();
+                self.bomb_planted = false;
+                self.bomb_defused = false;
+            }
+            MatchEvent::RoundEnd {
+                winner,
+                trigger,
+                ct_score,
+                t_score,
+            } => {
+                self.ct_score = *ct_score;
+                self.t_score = *t_score;
+                self.rounds.push(RoundResult {
+                    number: self.rounds.len() as u32 + 1,
+                    winner: *winner,
+                    reason: String::from(match_events::win_reason(trigger)),
+                    bomb_planted: self.bomb_planted,
+                    bomb_defused: self.bomb_defused,
+                });
+            }
+            MatchEvent::Kill {
+                killer,
+                victim,
+                headshot,
+                ..
+            } => {
+                let teamkill = killer.team.is_some() && killer.team == victim.team;
+                let stats = self.stats(killer);
+                if !teamkill {
+                    stats.kills += 1;
+                    stats.headshots += u32::from(*headshot);
+                }
+                self.stats(victim).deaths += 1;
+            }
+            MatchEvent::Assist { assister, .. } => self.stats(assister).assists += 1,
+            MatchEvent::Damage {
+                attacker,
+                victim,
+                damage,
+                health,
+            } => {
+                let left = self.health.insert(victim.key(), *health);
+                if attacker.team.is_some() && attacker.team == victim.team {
+                    return;
+                }
+                let dealt = (*damage).min(left.unwrap_or(FULL_HEALTH));
+                self.stats(attacker).damage += dealt;
+            }
+            MatchEvent::BombPlanted { player, .. } => {
+                self.bomb_planted = true;
+                self.stats(player);
+            }
+            MatchEvent::BombDefused { player } => {
+                self.bomb_defused = true;
+                self.stats(player);
+            }
+            MatchEvent::TeamSwitch { player, to, .. } => self.stats(player).team = *to,
+            MatchEvent::MatchEnd {
+                mode,
+                map,
+                ct_score,
+                t_score,
+                minutes,
+            } => {
+                self.mode = mode.clone();
+                self.minutes = *minutes;
+                self.map = map.clone();
+                self.ct_score = *ct_score;
+                self.t_score = *t_score;
+                self.finished = true;
+            }
+        }
+    }
+
+    // Average damage per round played so far
+    pub fn adr(&self, stats: &PlayerStats) -> f32 {
+        return stats.damage as f32 / self.rounds.len().max(1) as f32;
+    }
+
+    // Players of one team sorted by kills
+    pub fn team(&self, team: Team) -> Vec<&PlayerStats> {
+        let mut players: Vec<&PlayerStats> =
+            self.players.values().filter(|p| p.team == team).collect();
+        players.sort_by(|a, b| b.kills.cmp(&a.kills).then(a.deaths.cmp(&b.deaths)));
+        return players;
+    }
+
+    // Returns finished match once, so it is exported or stored only one time
+    pub fn take_finished(&mut self) -> Option<Scoreboard> {
+        if !self.finished || self.reported {
+            return None;
+        }
+        self.reported = true;
+        return Some(self.clone());
+    }
+
+    // Reads all pending lines from source, returns them with parsed events for other consumers
+    // like console view or demo recording
+    pub fn consume(&mut self, source: &mut dyn LineSource) -> (Vec<String>, Vec<MatchEvent>) {
+        let lines = source.read_lines();
+        let events: Vec<MatchEvent> = lines
+            .iter()
+            .filter_map(|line| match_events::parse_line(line))
+            .collect();
+        for event in &events {
+            self.apply(event);
+        }
+        return (lines, events);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::match_events::RecordedLog;
+
+    const RECORDED_MATCH: &str = include_str!("../testdata/cs2_match.log");
+
+    fn player<'a>(scoreboard: &'a Scoreboard, key: &str) -> &'a PlayerStats {
+        return scoreboard.players.get(key).unwrap();
+    }
+
+    #[test]
+    fn builds_scoreboard_from_recorded_log() {
+        let mut scoreboard = Scoreboard::default();
+        let (lines, events) = scoreboard.consume(&mut RecordedLog::new(RECORDED_MATCH));
+        assert_eq!(lines.len(), 20);
+        assert_eq!(events.len(), 20);
+
+        assert_eq!(scoreboard.map, "de_dust2");
+        assert_eq!(scoreboard.mode, "competitive");
+        assert_eq!(scoreboard.minutes, 3);
+        assert_eq!((scoreboard.ct_score, scoreboard.t_score), (1, 1));
+        assert!(scoreboard.finished);
+
+        // warmup kill before Match_Start is not counted
+        let veritaris = player(&scoreboard, "[U:1:123456]");
+        assert_eq!(veritaris.team, Team::CounterTerrorist);
+        assert_eq!(
+            (veritaris.kills, veritaris.deaths, veritaris.assists),
+            (0, 1, 1)
+        );
+        assert_eq!(veritaris.damage, 60);
+
+        // overkill damage is capped by health left
+        let sasha = player(&scoreboard, "[U:1:654321]");
+        assert_eq!((sasha.kills, sasha.deaths, sasha.headshots), (1, 1, 1));
+        assert_eq!(sasha.damage, 40);
+
+        let rezan = player(&scoreboard, "BOT Rezan");
+        assert_eq!(rezan.team, Team::Terrorist);
+        assert_eq!((rezan.kills, rezan.deaths, rezan.headshots), (2, 1, 0));
+        assert_eq!(rezan.damage, 127);
+        assert_eq!(scoreboard.adr(rezan), 63.5);
+
+        assert_eq!(scoreboard.rounds.len(), 2);
+        let first = &scoreboard.rounds[0];
+        assert_eq!(first.winner, Team::CounterTerrorist);
+        assert_eq!(first.reason, "Bomb defused");
+        assert!(first.bomb_planted && first.bomb_defused);
+        let second = &scoreboard.rounds[1];
+        assert_eq!(second.winner, Team::Terrorist);
+        assert_eq!(second.reason, "Elimination");
+        assert!(!second.bomb_planted);
+
+        let ct: Vec<&str> = scoreboard
+            .team(Team::CounterTerrorist)
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(ct, ["Sasha", "Veritaris"]);
+    }
+
+    #[test]
+    fn finished_match_is_taken_once() {
+        let mut scoreboard = Scoreboard::default();
+        scoreboard.consume(&mut RecordedLog::new(RECORDED_MATCH));
+        assert!(scoreboard.take_finished().is_some());
+        assert!(scoreboard.take_finished().is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::match_events::LineSource;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ExitStatus};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Instant;
+
+// Managed dedicated server with piped console
+pub struct ServerProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    output: Receiver<String>,
+    pub started_at: Instant,
+}
+
+fn forward_lines(source: impl Read + Send + 'static, sender: Sender<String>) {
+    thread::spawn(move || {
+        for chunk in BufReader::new(source).split(b'\n').map_while(Result::ok) {
+            let line = String::from_utf8_lossy(&chunk);
+            let line = line.trim_end();
+            if !line.is_empty() && sender.send(String::from(line)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+impl ServerProcess {
+    // Child must be spawned with piped stdin, stdout and stderr
+    pub fn new(mut child: Child) -> ServerProcess {
+        let (sender, receiver) = mpsc::channel::<String>();
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, sender);
+        }
+
+        return ServerProcess {
+            stdin: child.stdin.take(),
+            child,
+            output: receiver,
+            started_at: Instant::now(),
+        };
+    }
+
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        return self.child.try_wait();
+    }
+
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.child.kill()?;
+        self.child.wait()?;
+        return Ok(());
+    }
+
+    // Executes console command, same as typing it in server window
+    pub fn send_command(&mut self, command: &str) -> io::Result<()> {
+        let stdin = match &mut self.stdin {
+            Some(stdin) => stdin,
+            None => return Err(io::Error::from(io::ErrorKind::BrokenPipe)),
+        };
+        writeln!(stdin, "{command}")?;
+        return stdin.flush();
+    }
+}
+
+impl LineSource for ServerProcess {
+    fn read_lines(&mut self) -> Vec<String> {
+        return self.output.try_iter().collect();
+    }
+}
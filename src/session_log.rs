@@ -0,0 +1,262 @@
+use crate::utils;
+use std::cmp::Reverse;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const LOG_DIR_NAME: &str = &"logs";
+const LOG_EXT: &str = &"log";
+// sessions started within the same second get `_1`, `_2`, ... suffix
+const MAX_SAME_SECOND_SESSIONS: u32 = 100;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct LogRotation {
+    // session continues in next part file once current one grows over this size
+    pub max_file_mb: u64,
+    // older files are removed when new session starts, 0 keeps everything
+    pub max_age_days: u64,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        Self {
+            max_file_mb: 10,
+            max_age_days: 30,
+        }
+    }
+}
+
+pub struct LogFile {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+pub fn log_dir() -> Option<PathBuf> {
+    return utils::project_dirs().map(|dirs| dirs.data_dir().join(LOG_DIR_NAME));
+}
+
+// Log files of all sessions, newest first
+pub fn list(dir: &Path) -> Vec<LogFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<LogFile> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == LOG_EXT))
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            return Some(LogFile {
+                name: e.file_name().to_string_lossy().into_owned(),
+                path: e.path(),
+                size: metadata.len(),
+                modified: metadata.modified().ok()?,
+            });
+        })
+        .collect();
+    files.sort_by_key(|f| Reverse(f.modified));
+    return files;
+}
+
+pub fn prune(dir: &Path, max_age: Duration) {
+    let now = SystemTime::now();
+    for file in list(dir) {
+        let age = now.duration_since(file.modified).unwrap_or_default();
+        if age > max_age {
+            if let Err(err) = fs::remove_file(&file.path) {
+                log::warn!("Unable to remove old log {}: {err}", file.path.display());
+            }
+        }
+    }
+}
+
+// Byte ranges of lines in `content` containing `search`, case-insensitive, all lines if search is empty
+pub fn matching_lines(content: &str, search: &str) -> Vec<Range<usize>> {
+    let search = search.to_lowercase();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        if search.is_empty() || text.to_lowercase().contains(&search) {
+            ranges.push(start..start + text.len());
+        }
+        start += line.len();
+    }
+    return ranges;
+}
+
+// Never overwrites existing file
+fn create_new(path: &Path) -> io::Result<File> {
+    return OpenOptions::new().write(true).create_new(true).open(path);
+}
+
+// Copies log file to `target`, which must not exist yet
+pub fn export(path: &Path, target: &Path) -> io::Result<u64> {
+    let mut source = File::open(path)?;
+    return io::copy(&mut source, &mut create_new(target)?);
+}
+
+// Console output of one server run, split into parts by size
+pub struct SessionLog {
+    dir: PathBuf,
+    stem: String,
+    part: u32,
+    max_bytes: u64,
+    written: u64,
+    writer: BufWriter<File>,
+}
+
+impl SessionLog {
+    pub fn create(dir: &Path, rotation: &LogRotation) -> io::Result<SessionLog> {
+        fs::create_dir_all(dir)?;
+        if rotation.max_age_days > 0 {
+            prune(dir, Duration::from_secs(rotation.max_age_days * 86400));
+        }
+
+        // file names can't contain colons on Windows
+        let base = format!(
+            "session_{}",
+            utils::format_utc(SystemTime::now()).replace([' ', ':'], "-")
+        );
+        for index in 0..MAX_SAME_SECOND_SESSIONS {
+            let stem = match index {
+                0 => base.clone(),
+                _ => format!("{base}_{index}"),
+            };
+            let file = match create_new(&dir.join(format!("{stem}.{LOG_EXT}"))) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            };
+            return Ok(SessionLog {
+                dir: PathBuf::from(dir),
+                stem,
+                part: 0,
+                max_bytes: rotation.max_file_mb.max(1) * 1024 * 1024,
+                written: 0,
+                writer: BufWriter::new(file),
+            });
+        }
+        return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.part += 1;
+        let path = self
+            .dir
+            .join(format!("{}.{}.{LOG_EXT}", self.stem, self.part));
+        self.writer = BufWriter::new(create_new(&path)?);
+        self.written = 0;
+        return Ok(());
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.written >= self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.writer, "{line}")?;
+        self.written += line.len() as u64 + 1;
+        return Ok(());
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        return self.writer.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("session_log_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        return dir;
+    }
+
+    #[test]
+    fn filters_lines_case_insensitive() {
+        let content = "Host activate: de_dust2\r\nGC Connection established\nplayer KILLED bot\n";
+        let lines: Vec<&str> = matching_lines(content, "killed")
+            .into_iter()
+            .map(|range| &content[range])
+            .collect();
+        assert_eq!(lines, ["player KILLED bot"]);
+
+        let all: Vec<&str> = matching_lines(content, "")
+            .into_iter()
+            .map(|range| &content[range])
+            .collect();
+        assert_eq!(
+            all,
+            [
+                "Host activate: de_dust2",
+                "GC Connection established",
+                "player KILLED bot"
+            ]
+        );
+        assert!(matching_lines(content, "hibernating").is_empty());
+    }
+
+    #[test]
+    fn sessions_in_same_second_do_not_overwrite() {
+        let dir = temp_dir("same_second");
+        let rotation = LogRotation::default();
+
+        let mut first = SessionLog::create(&dir, &rotation).unwrap();
+        first.write_line("first").unwrap();
+        first.flush().unwrap();
+        let mut second = SessionLog::create(&dir, &rotation).unwrap();
+        second.write_line("second").unwrap();
+        second.flush().unwrap();
+
+        let mut contents: Vec<String> = list(&dir)
+            .iter()
+            .map(|file| fs::read_to_string(&file.path).unwrap())
+            .collect();
+        contents.sort();
+        assert_eq!(contents, ["first\n", "second\n"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotates_into_parts() {
+        let dir = temp_dir("rotation");
+        let rotation = LogRotation {
+            max_file_mb: 1,
+            max_age_days: 0,
+        };
+        let mut log = SessionLog::create(&dir, &rotation).unwrap();
+        let line = "x".repeat(1023);
+        for _ in 0..1100 {
+            log.write_line(&line).unwrap();
+        }
+        log.flush().unwrap();
+        assert_eq!(list(&dir).len(), 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn export_does_not_overwrite() {
+        let dir = temp_dir("export");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.log");
+        let target = dir.join("exported.log");
+        fs::write(&path, "line\n").unwrap();
+
+        assert_eq!(export(&path, &target).unwrap(), 5);
+        fs::write(&path, "changed\n").unwrap();
+        let err = export(&path, &target).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "line\n");
+        // exporting onto session log itself is refused too
+        assert!(export(&path, &path).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
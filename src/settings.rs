@@ -0,0 +1,259 @@
+use crate::duration::ConvarDuration;
+
+// Settings used to be stored at top level of app state, `map_name` is only found there in that layout
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+pub struct LegacyLayout {
+    pub map_name: Option<String>,
+}
+
+impl LegacyLayout {
+    pub fn is_legacy(&self) -> bool {
+        return self.map_name.is_some();
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct ServerSettings {
+    // label of registered installation to run, empty to use Steam detection or custom game path
+    pub installation: String,
+
+    pub hostname: String,
+
+    // comma separated
+    pub sv_tags: String,
+
+    //     255 - world
+    //     0 - US east coast, 1 - US west coast, 2 - South America, 3 - Europe
+    //     4 - Asia, 5 - Australia, 6 - Middle East, 7 - Africa
+    pub sv_region: u8,
+
+    // 1/0
+    pub sv_lan: bool,
+
+    // -maxplayers launch parameter
+    pub maxplayers: u32,
+
+    // -1 to show actual slots count
+    pub sv_visiblemaxplayers: i32,
+
+    // contents of game/csgo/motd.txt, left untouched if empty
+    pub motd: String,
+
+    // 1/0
+    pub insecure: bool,
+
+    // Game Server Login Token, empty for none
+    pub sv_setsteamaccount: String,
+
+    // 1/0
+    pub mp_autokick: bool,
+
+    //     0 - default, only in buy zones
+    //     1 - buy anywhere for all teams
+    //     2 - buy anywhere for terrorists only
+    //     3 - buy anywhere for counter-terrorists only
+    pub mp_buy_anywhere: u8,
+
+    pub mp_buytime: ConvarDuration,
+
+    pub mp_c4timer: ConvarDuration,
+
+    pub mp_freezetime: ConvarDuration,
+
+    // 1/0
+    pub mp_friendlyfire: bool,
+
+    // int
+    pub mp_maxrounds: u32,
+
+    //     0 - no random spawns
+    //     1 - everyone spawns in random locations (like deathmatch)
+    //     2 - only terrorists spawn at random locations, CTs spawn at their spawn
+    //     3 - only CTs spawn at random locations, terrorists spawn at their spawn
+    pub mp_randomspawn: u8,
+
+    pub mp_roundtime: ConvarDuration,
+
+    pub mp_roundtime_defuse: ConvarDuration,
+
+    pub mp_roundtime_hostage: ConvarDuration,
+
+    pub mp_warmuptime: ConvarDuration,
+
+    pub mp_endwarmup_player_count: u32,
+
+    // number, tickrate
+    pub sv_minupdaterate: u32,
+
+    // "0" to disable or password
+    pub sv_password: String,
+
+    pub game_alias: String,
+
+    pub map_name: String,
+
+    // team names shown in scoreboard and used in demo names, empty for default
+    pub mp_teamname_1: String,
+
+    pub mp_teamname_2: String,
+
+    // 1/0, GOTV relay, has to be enabled before map is loaded
+    pub tv_enable: bool,
+
+    // broadcast delay
+    pub tv_delay: ConvarDuration,
+
+    pub tv_port: u16,
+
+    pub tv_name: String,
+
+    // 1/0, engine records every match itself, auto recording below is skipped then
+    pub tv_autorecord: bool,
+
+    pub tv_maxclients: u32,
+
+    // `tv_record` on match start and `tv_stoprecord` on match end through server console
+    pub auto_record_demos: bool,
+}
+
+impl ServerSettings {
+    // Settings to share with others, login token is secret and left out unless asked for
+    pub fn export(&self, include_login_token: bool) -> serde_json::Result<String> {
+        let mut exported = self.clone();
+        if !include_login_token {
+            exported.sv_setsteamaccount.clear();
+        }
+        return serde_json::to_string_pretty(&exported);
+    }
+
+    // Own login token is kept when imported file has none
+    pub fn import(&self, content: &str) -> serde_json::Result<ServerSettings> {
+        let mut imported: ServerSettings = serde_json::from_str(content)?;
+        if imported.sv_setsteamaccount.is_empty() {
+            imported.sv_setsteamaccount = self.sv_setsteamaccount.clone();
+        }
+        return Ok(imported);
+    }
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            installation: "".to_string(),
+            map_name: "de_dust2".to_string(),
+
+            hostname: "CS2 Server".to_string(),
+            sv_tags: "".to_string(),
+            sv_region: 255,
+            sv_lan: false,
+            maxplayers: 10,
+            sv_visiblemaxplayers: -1,
+            motd: "".to_string(),
+
+            insecure: true,
+            sv_setsteamaccount: "".to_string(),
+            mp_autokick: true,
+            mp_buy_anywhere: 0u8,
+            mp_buytime: ConvarDuration::from_secs(15),
+            mp_c4timer: ConvarDuration::from_secs(40),
+            mp_freezetime: ConvarDuration::from_secs(20),
+            mp_friendlyfire: true,
+            mp_maxrounds: 32,
+            mp_endwarmup_player_count: 2,
+            mp_randomspawn: 0,
+            mp_roundtime: ConvarDuration::from_secs(115),
+            mp_roundtime_defuse: ConvarDuration::from_secs(115),
+            mp_roundtime_hostage: ConvarDuration::from_secs(115),
+            mp_warmuptime: ConvarDuration::from_secs(15),
+            sv_minupdaterate: 64,
+            sv_password: "0".to_string(),
+
+            game_alias: "competitive".to_string(),
+
+            mp_teamname_1: "".to_string(),
+            mp_teamname_2: "".to_string(),
+
+            tv_enable: false,
+            tv_delay: ConvarDuration::from_secs(10),
+            tv_port: 27020,
+            tv_name: "GOTV".to_string(),
+            tv_autorecord: false,
+            tv_maxclients: 10,
+            auto_record_demos: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // app state as stored before settings moved under `settings`
+    const LEGACY_STATE: &str = r#"{
+        "app_id": 730,
+        "insecure": false,
+        "mp_roundtime": 120,
+        "mp_maxrounds": 24,
+        "sv_password": "secret",
+        "game_alias": "wingman",
+        "map_name": "de_inferno",
+        "game_path_str": "C:\\CS2"
+    }"#;
+
+    #[test]
+    fn reads_legacy_top_level_settings() {
+        let layout: LegacyLayout = serde_json::from_str(LEGACY_STATE).unwrap();
+        assert!(layout.is_legacy());
+
+        let settings: ServerSettings = serde_json::from_str(LEGACY_STATE).unwrap();
+        assert!(!settings.insecure);
+        assert_eq!(settings.mp_roundtime, ConvarDuration::from_secs(120));
+        assert_eq!(settings.mp_maxrounds, 24);
+        assert_eq!(settings.sv_password, "secret");
+        assert_eq!(settings.game_alias, "wingman");
+        assert_eq!(settings.map_name, "de_inferno");
+        // missing in old layout, stays default
+        assert_eq!(settings.tv_port, 27020);
+    }
+
+    #[test]
+    fn export_leaves_login_token_out_by_default() {
+        let settings = ServerSettings {
+            sv_setsteamaccount: String::from("0123456789ABCDEF0123456789ABCDEF"),
+            ..ServerSettings::default()
+        };
+
+        let exported = settings.export(false).unwrap();
+        assert!(!exported.contains("0123456789ABCDEF"));
+        let imported: ServerSettings = serde_json::from_str(&exported).unwrap();
+        assert_eq!(imported.sv_setsteamaccount, "");
+
+        let exported = settings.export(true).unwrap();
+        assert!(exported.contains("0123456789ABCDEF0123456789ABCDEF"));
+    }
+
+    #[test]
+    fn import_keeps_own_login_token() {
+        let own = ServerSettings {
+            sv_setsteamaccount: String::from("own-token"),
+            ..ServerSettings::default()
+        };
+        let shared = ServerSettings {
+            map_name: String::from("de_anubis"),
+            ..ServerSettings::default()
+        };
+
+        let imported = own.import(&shared.export(false).unwrap()).unwrap();
+        assert_eq!(imported.map_name, "de_anubis");
+        assert_eq!(imported.sv_setsteamaccount, "own-token");
+    }
+
+    #[test]
+    fn current_layout_is_not_legacy() {
+        let state = r#"{"app_id": 730, "settings": {"map_name": "de_nuke"}}"#;
+        let layout: LegacyLayout = serde_json::from_str(state).unwrap();
+        assert!(!layout.is_legacy());
+    }
+}
@@ -0,0 +1,330 @@
+use crate::match_export::MatchSummary;
+use crate::utils;
+use linked_hash_map::LinkedHashMap;
+use rusqlite::{params, Connection};
+use std::fs;
+use std::path::PathBuf;
+
+const DB_FILE_NAME: &str = &"stats.sqlite3";
+const BOT_PREFIX: &str = &"BOT";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS matches (
+    id INTEGER PRIMARY KEY,
+    finished_at TEXT NOT NULL,
+    map TEXT NOT NULL,
+    mode TEXT NOT NULL,
+    ct_score INTEGER NOT NULL,
+    t_score INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS player_stats (
+    match_id INTEGER NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    steam_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    team TEXT NOT NULL,
+    won INTEGER NOT NULL,
+    kills INTEGER NOT NULL,
+    deaths INTEGER NOT NULL,
+    assists INTEGER NOT NULL,
+    damage INTEGER NOT NULL,
+    PRIMARY KEY (match_id, steam_id)
+);
+CREATE INDEX IF NOT EXISTS matches_finished_at ON matches(finished_at);
+";
+
+pub struct LeaderboardEntry {
+    pub steam_id: String,
+    pub name: String,
+    pub matches: u32,
+    pub wins: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub maps_played: u32,
+    // map with best win rate, more matches wins a tie
+    pub best_map: String,
+}
+
+impl LeaderboardEntry {
+    pub fn kd(&self) -> f32 {
+        return self.kills as f32 / self.deaths.max(1) as f32;
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        return self.wins as f32 / self.matches.max(1) as f32;
+    }
+}
+
+// Long-term player stats across matches, keyed by SteamID
+pub struct StatsDb {
+    connection: Connection,
+}
+
+pub fn db_path() -> Option<PathBuf> {
+    return utils::project_dirs().map(|dirs| dirs.data_dir().join(DB_FILE_NAME));
+}
+
+fn won(team: &str, summary: &MatchSummary) -> bool {
+    // final score is reported per side players are on after last half
+    return match team {
+        "CT" => summary.ct_score > summary.t_score,
+        "T" => summary.t_score > summary.ct_score,
+        _ => false,
+    };
+}
+
+impl StatsDb {
+    pub fn open() -> rusqlite::Result<StatsDb> {
+        let path = db_path().ok_or(rusqlite::Error::InvalidPath(PathBuf::from(DB_FILE_NAME)))?;
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        return StatsDb::with_connection(Connection::open(path)?);
+    }
+
+    fn with_connection(connection: Connection) -> rusqlite::Result<StatsDb> {
+        // SQLite ignores ON DELETE CASCADE unless enabled for every connection
+        connection.execute_batch("PRAGMA foreign_keys = ON;")?;
+        connection.execute_batch(SCHEMA)?;
+        return Ok(StatsDb { connection });
+    }
+
+    // Bots are not stored
+    pub fn record_match(&mut self, summary: &MatchSummary) -> rusqlite::Result<()> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO matches (finished_at, map, mode, ct_score, t_score) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                summary.finished_at,
+                summary.map,
+                summary.mode,
+                summary.ct_score,
+                summary.t_score
+            ],
+        )?;
+        let match_id = transaction.last_insert_rowid();
+
+        for player in &summary.players {
+            if player.steam_id.starts_with(BOT_PREFIX) {
+                continue;
+            }
+            transaction.execute(
+                "INSERT OR REPLACE INTO player_stats
+                    (match_id, steam_id, name, team, won, kills, deaths, assists, damage)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    match_id,
+                    player.steam_id,
+                    player.name,
+                    player.team,
+                    won(&player.team, summary),
+                    player.kills,
+                    player.deaths,
+                    player.assists,
+                    player.damage
+                ],
+            )?;
+        }
+        return transaction.commit();
+    }
+
+    // Dates are `YYYY-MM-DD`, both inclusive, empty means unbounded
+    pub fn leaderboard(&self, from: &str, to: &str) -> rusqlite::Result<Vec<LeaderboardEntry>> {
+        let mut statement = self.connection.prepare(
+            "SELECT p.steam_id,
+                (SELECT latest.name FROM player_stats latest JOIN matches lm ON lm.id = latest.match_id
+                    WHERE latest.steam_id = p.steam_id ORDER BY lm.finished_at DESC, lm.id DESC LIMIT 1),
+                m.map, COUNT(*), SUM(p.won), SUM(p.kills), SUM(p.deaths), SUM(p.assists)
+                FROM player_stats p JOIN matches m ON m.id = p.match_id
+                WHERE m.finished_at >= ?1 AND m.finished_at <= ?2
+                GROUP BY p.steam_id, m.map",
+        )?;
+        let to = if to.is_empty() {
+            String::from("9999")
+        } else {
+            format!("{to} 23:59:59")
+        };
+        let rows = statement.query_map(params![from, to], |row| {
+            return Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                [
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, u32>(4)?,
+                    row.get::<_, u32>(5)?,
+                    row.get::<_, u32>(6)?,
+                    row.get::<_, u32>(7)?,
+                ],
+            ));
+        })?;
+
+        // per map rows are folded into one entry per player
+        let mut entries: LinkedHashMap<String, (LeaderboardEntry, (f32, u32))> =
+            LinkedHashMap::new();
+        for row in rows {
+            let (steam_id, name, map, [matches, wins, kills, deaths, assists]) = row?;
+            let (entry, best) = entries.entry(steam_id.clone()).or_insert_with(|| {
+                (
+                    LeaderboardEntry {
+                        steam_id,
+                        name,
+                        matches: 0,
+                        wins: 0,
+                        kills: 0,
+                        deaths: 0,
+                        assists: 0,
+                        maps_played: 0,
+                        best_map: String::new(),
+                    },
+                    (-1.0, 0),
+                )
+            });
+            entry.matches += matches;
+            entry.wins += wins;
+            entry.kills += kills;
+            entry.deaths += deaths;
+            entry.assists += assists;
+            entry.maps_played += 1;
+
+            let map_rate = (wins as f32 / matches.max(1) as f32, matches);
+            if map_rate.0 > best.0 || (map_rate.0 == best.0 && map_rate.1 > best.1) {
+                *best = map_rate;
+                entry.best_map = map;
+            }
+        }
+
+        let mut leaderboard: Vec<LeaderboardEntry> =
+            entries.into_iter().map(|(_, (entry, _))| entry).collect();
+        leaderboard.sort_by(|a, b| b.kd().total_cmp(&a.kd()));
+        return Ok(leaderboard);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::match_export::PlayerSummary;
+
+    fn player(steam_id: &str, name: &str, team: &str, kills: u32, deaths: u32) -> PlayerSummary {
+        return PlayerSummary {
+            steam_id: String::from(steam_id),
+            name: String::from(name),
+            team: String::from(team),
+            kills,
+            deaths,
+            assists: 0,
+            headshots: 0,
+            damage: 0,
+            adr: 0.0,
+        };
+    }
+
+    fn summary(finished_at: &str, map: &str, ct_score: u32, t_score: u32) -> MatchSummary {
+        return MatchSummary {
+            finished_at: String::from(finished_at),
+            map: String::from(map),
+            mode: String::from("competitive"),
+            minutes: 40,
+            ct_score,
+            t_score,
+            rounds: Vec::new(),
+            players: Vec::new(),
+        };
+    }
+
+    fn db() -> StatsDb {
+        return StatsDb::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn bots_are_not_stored() {
+        let mut db = db();
+        let mut played = summary("2026-10-01 20:00:00", "de_dust2", 13, 5);
+        played.players = vec![
+            player("[U:1:1]", "Sasha", "CT", 20, 10),
+            player("BOT Rezan", "Rezan", "T", 10, 20),
+        ];
+        db.record_match(&played).unwrap();
+
+        let leaderboard = db.leaderboard("", "").unwrap();
+        assert_eq!(leaderboard.len(), 1);
+        assert_eq!(leaderboard[0].steam_id, "[U:1:1]");
+        assert_eq!(leaderboard[0].wins, 1);
+    }
+
+    #[test]
+    fn date_range_is_inclusive() {
+        let mut db = db();
+        for finished_at in [
+            "2026-09-30 23:59:59",
+            "2026-10-01 00:00:00",
+            "2026-10-31 23:59:59",
+            "2026-11-01 00:00:00",
+        ] {
+            let mut played = summary(finished_at, "de_dust2", 13, 5);
+            played.players = vec![player("[U:1:1]", "Sasha", "CT", 1, 1)];
+            db.record_match(&played).unwrap();
+        }
+
+        let matches = |from: &str, to: &str| db.leaderboard(from, to).unwrap()[0].matches;
+        assert_eq!(matches("2026-10-01", "2026-10-31"), 2);
+        assert_eq!(matches("", "2026-10-31"), 3);
+        assert_eq!(matches("2026-10-01", ""), 3);
+        assert_eq!(matches("", ""), 4);
+        assert!(db.leaderboard("2026-12-01", "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn best_map_prefers_win_rate_then_matches() {
+        let mut db = db();
+        // inferno 1/2, dust2 2/2, mirage 1/1
+        for (map, ct_score, t_score) in [
+            ("de_inferno", 13, 5),
+            ("de_inferno", 5, 13),
+            ("de_mirage", 13, 7),
+            ("de_dust2", 13, 5),
+            ("de_dust2", 13, 11),
+        ] {
+            let mut played = summary("2026-10-01 20:00:00", map, ct_score, t_score);
+            played.players = vec![player("[U:1:1]", "Sasha", "CT", 1, 1)];
+            db.record_match(&played).unwrap();
+        }
+
+        let entry = &db.leaderboard("", "").unwrap()[0];
+        assert_eq!(entry.best_map, "de_dust2");
+        assert_eq!(entry.maps_played, 3);
+        assert_eq!((entry.matches, entry.wins), (5, 4));
+    }
+
+    #[test]
+    fn name_comes_from_latest_match() {
+        let mut db = db();
+        for (finished_at, name) in [
+            ("2026-10-02 20:00:00", "Zed"),
+            ("2026-10-03 20:00:00", "Alice"),
+            ("2026-10-01 20:00:00", "Bob"),
+        ] {
+            let mut played = summary(finished_at, "de_dust2", 13, 5);
+            played.players = vec![player("[U:1:1]", name, "CT", 1, 1)];
+            db.record_match(&played).unwrap();
+        }
+
+        assert_eq!(db.leaderboard("", "").unwrap()[0].name, "Alice");
+    }
+
+    #[test]
+    fn deleting_match_removes_player_stats() {
+        let mut db = db();
+        let mut played = summary("2026-10-01 20:00:00", "de_dust2", 13, 5);
+        played.players = vec![player("[U:1:1]", "Sasha", "CT", 1, 1)];
+        db.record_match(&played).unwrap();
+
+        db.connection.execute("DELETE FROM matches", []).unwrap();
+        let count: u32 = db
+            .connection
+            .query_row("SELECT COUNT(*) FROM player_stats", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+}
@@ -1,6 +1,19 @@
+use crate::keyvalues;
 use linked_hash_map::LinkedHashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, PartialEq)]
+// AppState flags from appmanifest_*.acf
+const STATE_UPDATE_REQUIRED: u32 = 2;
+const STATE_FULLY_INSTALLED: u32 = 4;
+const STATE_FILES_MISSING: u32 = 32;
+const STATE_FILES_CORRUPT: u32 = 128;
+const STATE_UPDATE_RUNNING: u32 = 256;
+const STATE_UPDATE_PAUSED: u32 = 512;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ReadyState {
     NoSteam,
@@ -12,45 +25,318 @@ pub enum ReadyState {
 pub enum ServerCreationError {
     NoExecutableFound,
     UnsupportedOS,
+    SpawnFailed(io::Error),
+}
+
+impl fmt::Display for ServerCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            ServerCreationError::NoExecutableFound => write!(f, "Server executable not found"),
+            ServerCreationError::UnsupportedOS => {
+                write!(f, "Server can't run on this operating system")
+            }
+            ServerCreationError::SpawnFailed(err) => write!(f, "Unable to start process: {err}"),
+        };
+    }
+}
+
+#[derive(Debug)]
+pub enum DiscoveryError {
+    SteamNotFound(steamlocate::Error),
+    UnreadableLibraryFolders(steamlocate::Error),
+    NonUtf8Path(PathBuf),
+    MissingApp(u32),
+    MissingGameDir(PathBuf),
+    IncompleteInstall(PathBuf),
+    UnsupportedGame(u32),
+}
+
+impl fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            DiscoveryError::SteamNotFound(err) => write!(
+                f,
+                "Unable to find Steam directory. Are you sure you have Steam installed? ({err})"
+            ),
+            DiscoveryError::UnreadableLibraryFolders(err) => {
+                write!(f, "Unable to read Steam library folders: {err}")
+            }
+            DiscoveryError::NonUtf8Path(path) => write!(
+                f,
+                "Path contains non UTF-8 characters and was skipped: {}",
+                path.display()
+            ),
+            DiscoveryError::MissingApp(app_id) => write!(
+                f,
+                "Unable to find app with id {app_id}. Are you sure it is installed?"
+            ),
+            DiscoveryError::MissingGameDir(path) => {
+                write!(f, "Game directory does not exist: {}", path.display())
+            }
+            DiscoveryError::IncompleteInstall(path) => write!(
+                f,
+                "Game installation is incomplete, see checklist below: {}",
+                path.display()
+            ),
+            DiscoveryError::UnsupportedGame(app_id) => {
+                write!(f, "App with id {app_id} is not a supported game server")
+            }
+        };
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct SteamApp {
+    pub app_id: u32,
+    pub name: Option<String>,
+    pub install_dir: String,
+}
+
+// Everything found in Steam libraries, broken entries are reported in `problems` and skipped
+#[derive(Default)]
+pub struct Discovery {
+    pub steam_found: bool,
+    pub apps: LinkedHashMap<u32, SteamApp>,
+    pub problems: Vec<DiscoveryError>,
+}
+
+impl Discovery {
+    pub fn app_dir(&self, app_id: &u32) -> Result<String, DiscoveryError> {
+        return match self.apps.get(app_id) {
+            None => Err(DiscoveryError::MissingApp(*app_id)),
+            Some(app) => Ok(String::from(&app.install_dir)),
+        };
+    }
 }
 
-pub fn get_available_apps() -> Result<LinkedHashMap<u32, steamlocate::App>, steamlocate::Error> {
-    let steam_dir = steamlocate::SteamDir::locate()?;
-    let mut available_apps: LinkedHashMap<u32, steamlocate::App> = LinkedHashMap::new();
+pub fn discover(progress: &dyn Fn(String)) -> Discovery {
+    let mut discovery = Discovery::default();
+
+    progress(String::from("Locating Steam"));
+    let steam_dir = match steamlocate::SteamDir::locate() {
+        Ok(res) => res,
+        Err(err) => {
+            discovery.problems.push(DiscoveryError::SteamNotFound(err));
+            return discovery;
+        }
+    };
+    discovery.steam_found = true;
+
+    let libraries = match steam_dir.libraries() {
+        Ok(res) => res,
+        Err(err) => {
+            discovery
+                .problems
+                .push(DiscoveryError::UnreadableLibraryFolders(err));
+            return discovery;
+        }
+    };
 
-    for library in steam_dir.libraries()? {
-        let library = library?;
+    let mut available_apps: Vec<(u32, SteamApp)> = Vec::new();
+
+    for (index, library) in libraries.enumerate() {
+        progress(format!("Reading Steam library #{}", index + 1));
+
+        let library = match library {
+            Ok(res) => res,
+            Err(err) => {
+                discovery
+                    .problems
+                    .push(DiscoveryError::UnreadableLibraryFolders(err));
+                continue;
+            }
+        };
 
         for app in library.apps() {
-            let mut app = app?;
-            app.install_dir = String::from(library.resolve_app_dir(&app).to_str().unwrap());
-            available_apps.insert(app.app_id, app.clone());
+            let app = match app {
+                Ok(res) => res,
+                Err(err) => {
+                    discovery
+                        .problems
+                        .push(DiscoveryError::UnreadableLibraryFolders(err));
+                    continue;
+                }
+            };
+
+            let app_dir = library.resolve_app_dir(&app);
+            let install_dir = match app_dir.to_str() {
+                Some(res) => String::from(res),
+                None => {
+                    discovery
+                        .problems
+                        .push(DiscoveryError::NonUtf8Path(app_dir));
+                    continue;
+                }
+            };
+            available_apps.push((
+                app.app_id,
+                SteamApp {
+                    app_id: app.app_id,
+                    name: app.name,
+                    install_dir,
+                },
+            ));
         }
     }
 
-    let mut sorted: Vec<(u32, steamlocate::App)> =
-        available_apps.iter().map(|t| (*t.0, t.1.clone())).collect();
+    available_apps.sort_by_key(|e| e.0);
+    discovery.apps = LinkedHashMap::from_iter(available_apps);
+    progress(format!("Found {} installed apps", discovery.apps.len()));
 
-    sorted.sort_by_key(|e| e.0);
-    available_apps = LinkedHashMap::from_iter(sorted);
+    return discovery;
+}
 
-    return Ok(available_apps);
+pub struct AppManifest {
+    pub state_flags: u32,
+    pub build_id: String,
+    pub update_result: u32,
 }
 
-pub fn get_steam_apps_id_with_paths() -> Result<LinkedHashMap<u32, String>, steamlocate::Error> {
-    return Ok(LinkedHashMap::from_iter(
-        get_available_apps()?
-            .iter()
-            .map(|e| (*e.0, e.1.clone().install_dir))
-            .collect::<Vec<(u32, String)>>(),
-    ));
+impl AppManifest {
+    // Steam library keeps manifest two levels above `<library>/steamapps/common/<game>`,
+    // SteamCMD `force_install_dir` keeps it in `<game>/steamapps`
+    pub fn for_install_dir(install_dir: &String, app_id: &u32) -> Option<AppManifest> {
+        let file_name = format!("appmanifest_{app_id}.acf");
+        let install_dir = Path::new(install_dir);
+        let library = install_dir.parent().and_then(|p| p.parent());
+        let content = [
+            Some(install_dir.join("steamapps")),
+            library.map(Path::to_path_buf),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|dir| fs::read_to_string(dir.join(&file_name)).ok())?;
+        return AppManifest::parse(&content);
+    }
+
+    pub fn parse(content: &str) -> Option<AppManifest> {
+        let root = keyvalues::parse(content)?;
+        let state = root.get_section("AppState")?;
+
+        return Some(AppManifest {
+            state_flags: state.get_parsed("StateFlags").unwrap_or(0),
+            build_id: String::from(state.get_str("buildid").unwrap_or("")),
+            update_result: state.get_parsed("UpdateResult").unwrap_or(0),
+        });
+    }
+
+    fn has_flag(&self, flag: u32) -> bool {
+        return self.state_flags & flag != 0;
+    }
+
+    // Reasons why launching this install may fail, empty if Steam reports it as ready
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+
+        if self.has_flag(STATE_UPDATE_RUNNING) || self.has_flag(STATE_UPDATE_PAUSED) {
+            problems.push(String::from("Steam is updating the game right now"));
+        } else if self.has_flag(STATE_UPDATE_REQUIRED) {
+            problems.push(String::from("Steam reports that game update is pending"));
+        }
+        if !self.has_flag(STATE_FULLY_INSTALLED)
+            || self.has_flag(STATE_FILES_MISSING)
+            || self.has_flag(STATE_FILES_CORRUPT)
+        {
+            problems.push(String::from(
+                "Steam reports installation as incomplete, verify integrity of game files",
+            ));
+        }
+        if self.update_result != 0 {
+            problems.push(format!(
+                "Last update failed with code {}",
+                self.update_result
+            ));
+        }
+
+        return problems;
+    }
 }
 
-pub fn get_steam_dir_for_app(target_app_id: &u32) -> Result<String, steamlocate::Error> {
-    return match get_steam_apps_id_with_paths()?.get(&target_app_id) {
-        None => Err(steamlocate::Error::MissingExpectedApp {
-            app_id: *target_app_id,
-        }),
-        Some(res) => Ok(String::from(res)),
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APP_MANIFEST: &str = include_str!("../testdata/appmanifest_730.acf");
+
+    #[test]
+    fn reports_pending_update() {
+        let manifest = AppManifest::parse(APP_MANIFEST).unwrap();
+        assert_eq!(manifest.state_flags, 6);
+        assert_eq!(manifest.build_id, "20348731");
+        assert_eq!(manifest.update_result, 12);
+        assert_eq!(
+            manifest.problems(),
+            [
+                "Steam reports that game update is pending",
+                "Last update failed with code 12",
+            ]
+        );
+    }
+
+    #[test]
+    fn ready_install_has_no_problems() {
+        let content = APP_MANIFEST
+            .replace("\"StateFlags\"\t\t\"6\"", "\"StateFlags\"\t\t\"4\"")
+            .replace("\"UpdateResult\"\t\t\"12\"", "\"UpdateResult\"\t\t\"0\"");
+        assert!(AppManifest::parse(&content).unwrap().problems().is_empty());
+    }
+
+    #[test]
+    fn reports_incomplete_install() {
+        for flags in ["2", "36", "132"] {
+            let content = APP_MANIFEST.replace(
+                "\"StateFlags\"\t\t\"6\"",
+                &format!("\"StateFlags\"\t\t\"{flags}\""),
+            );
+            let problems = AppManifest::parse(&content).unwrap().problems();
+            assert!(problems.iter().any(|p| p.contains("incomplete")), "{flags}");
+        }
+        let updating = APP_MANIFEST.replace("\"StateFlags\"\t\t\"6\"", "\"StateFlags\"\t\t\"262\"");
+        assert_eq!(
+            AppManifest::parse(&updating).unwrap().problems()[0],
+            "Steam is updating the game right now"
+        );
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("steam_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        return dir;
+    }
+
+    #[test]
+    fn finds_manifest_of_steam_library_install() {
+        let library = temp_dir("library").join("steamapps");
+        let install_dir = library
+            .join("common")
+            .join("Counter-Strike Global Offensive");
+        fs::create_dir_all(&install_dir).unwrap();
+        fs::write(library.join("appmanifest_730.acf"), APP_MANIFEST).unwrap();
+
+        let install_dir = String::from(install_dir.to_str().unwrap());
+        let manifest = AppManifest::for_install_dir(&install_dir, &730).unwrap();
+        assert_eq!(manifest.build_id, "20348731");
+        assert!(AppManifest::for_install_dir(&install_dir, &740).is_none());
+    }
+
+    #[test]
+    fn finds_manifest_of_steamcmd_install() {
+        let install_dir = temp_dir("steamcmd").join("cs2-ds");
+        fs::create_dir_all(install_dir.join("steamapps")).unwrap();
+        fs::write(
+            install_dir.join("steamapps").join("appmanifest_730.acf"),
+            APP_MANIFEST,
+        )
+        .unwrap();
+
+        let install_dir = String::from(install_dir.to_str().unwrap());
+        let manifest = AppManifest::for_install_dir(&install_dir, &730).unwrap();
+        assert_eq!(manifest.build_id, "20348731");
+    }
+
+    #[test]
+    fn manifest_without_app_state_is_ignored() {
+        assert!(AppManifest::parse("\"AppState\" {").is_none());
+        assert!(AppManifest::parse("\"LibraryFolders\" { }").is_none());
+    }
 }
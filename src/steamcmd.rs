@@ -0,0 +1,265 @@
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+const PROGRESS_MARKER: &str = &"Update state";
+const SUCCESS_MARKER: &str = &"Success!";
+const ERROR_MARKER: &str = &"Error!";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    // e.g. "downloading", "verifying install", "preallocating"
+    pub state: String,
+    pub percent: f32,
+    pub current: u64,
+    pub total: u64,
+}
+
+pub enum SteamCmdEvent {
+    Output(String),
+    Progress(Progress),
+    Finished(Result<(), String>),
+}
+
+pub fn build_args(install_dir: &Path, app_id: u32, validate: bool) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        String::from("+force_install_dir"),
+        install_dir.display().to_string(),
+        String::from("+login"),
+        String::from("anonymous"),
+        String::from("+app_update"),
+        app_id.to_string(),
+    ];
+    if validate {
+        args.push(String::from("validate"));
+    }
+    args.push(String::from("+quit"));
+    return args;
+}
+
+// Parses lines like:
+//     Update state (0x61) downloading, progress: 45.67 (1234567 / 2702222)
+pub fn parse_progress(line: &str) -> Option<Progress> {
+    let line = line.trim();
+    let rest = line.strip_prefix(PROGRESS_MARKER)?;
+    let (_, rest) = rest.split_once(')')?;
+    let (state, rest) = rest.split_once(", progress:")?;
+    let (percent, rest) = rest.trim().split_once(' ')?;
+    let (current, total) = rest
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split_once('/')?;
+
+    return Some(Progress {
+        state: String::from(state.trim()),
+        percent: percent.trim().parse().ok()?,
+        current: current.trim().parse().ok()?,
+        total: total.trim().parse().ok()?,
+    });
+}
+
+// Runs steamcmd and streams its output, the last event is always `Finished`
+pub fn start(
+    executable: &Path,
+    install_dir: &Path,
+    app_id: u32,
+    validate: bool,
+) -> io::Result<Receiver<SteamCmdEvent>> {
+    let mut child = Command::new(executable)
+        .args(build_args(install_dir, app_id, validate))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let (sender, receiver) = mpsc::channel::<SteamCmdEvent>();
+
+    thread::spawn(move || {
+        // stderr has the reason when steamcmd fails before printing `Error!`, e.g. missing libraries
+        let stderr_reader = stderr.map(|stderr| {
+            let sender = sender.clone();
+            return thread::spawn(move || {
+                let mut last_line: Option<String> = None;
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    log::warn!("steamcmd: {line}");
+                    let _ = sender.send(SteamCmdEvent::Output(String::from(line)));
+                    last_line = Some(String::from(line));
+                }
+                return last_line;
+            });
+        });
+        let mut succeeded = false;
+        let mut last_error: Option<String> = None;
+
+        if let Some(stdout) = stdout {
+            // steamcmd redraws progress with carriage returns
+            for chunk in BufReader::new(stdout).split(b'\n').map_while(Result::ok) {
+                for line in String::from_utf8_lossy(&chunk).split('\r') {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    if line.starts_with(SUCCESS_MARKER) {
+                        succeeded = true;
+                    }
+                    if line.starts_with(ERROR_MARKER) {
+                        last_error = Some(String::from(line));
+                    }
+                    if let Some(progress) = parse_progress(line) {
+                        let _ = sender.send(SteamCmdEvent::Progress(progress));
+                    }
+                    let _ = sender.send(SteamCmdEvent::Output(String::from(line)));
+                }
+            }
+        }
+
+        let stderr_tail = stderr_reader.and_then(|reader| reader.join().ok().flatten());
+        let result = match child.wait() {
+            // exit code is unreliable, steamcmd returns non-zero after self-update
+            Ok(_) if succeeded && last_error.is_none() => Ok(()),
+            Ok(status) => Err(last_error
+                .or(stderr_tail)
+                .unwrap_or_else(|| format!("steamcmd exited with {status}"))),
+            Err(err) => Err(format!("Unable to wait for steamcmd: {err}")),
+        };
+        let _ = sender.send(SteamCmdEvent::Finished(result));
+    });
+
+    return Ok(receiver);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_progress_lines() {
+        let progress = parse_progress(
+            " Update state (0x61) downloading, progress: 12.34 (4138732 / 33540063)",
+        )
+        .unwrap();
+        assert_eq!(progress.state, "downloading");
+        assert_eq!(progress.percent, 12.34);
+        assert_eq!(progress.current, 4138732);
+        assert_eq!(progress.total, 33540063);
+
+        let progress =
+            parse_progress("Update state (0x81) verifying update, progress: 100.00 (100 / 100)")
+                .unwrap();
+        assert_eq!(progress.state, "verifying update");
+        assert_eq!(progress.percent, 100.0);
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(parse_progress("Success! App '730' fully installed."), None);
+        assert_eq!(parse_progress("Update state (0x61) downloading"), None);
+        assert_eq!(
+            parse_progress("Update state (0x61) downloading, progress: x (1 / 2)"),
+            None
+        );
+        assert_eq!(parse_progress(""), None);
+    }
+
+    #[test]
+    fn builds_args() {
+        let args = build_args(Path::new("/srv/cs2"), 730, true);
+        assert_eq!(
+            args,
+            [
+                "+force_install_dir",
+                "/srv/cs2",
+                "+login",
+                "anonymous",
+                "+app_update",
+                "730",
+                "validate",
+                "+quit"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    fn stub_steamcmd(name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("steamcmd_stub_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        return path;
+    }
+
+    #[cfg(unix)]
+    fn run(executable: &Path) -> (Vec<Progress>, Vec<String>, Result<(), String>) {
+        let receiver = start(executable, Path::new("/tmp/cs2"), 730, false).unwrap();
+        let mut progress: Vec<Progress> = Vec::new();
+        let mut output: Vec<String> = Vec::new();
+        for event in receiver {
+            match event {
+                SteamCmdEvent::Progress(p) => progress.push(p),
+                SteamCmdEvent::Output(line) => output.push(line),
+                SteamCmdEvent::Finished(result) => return (progress, output, result),
+            }
+        }
+        panic!("no Finished event");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stub_install_succeeds() {
+        let stub = stub_steamcmd(
+            "success.sh",
+            "printf 'Update state (0x61) downloading, progress: 12.34 (10 / 81)\\r'\n\
+             printf 'Update state (0x61) downloading, progress: 50.00 (40 / 81)\\n'\n\
+             echo \"Success! App '730' fully installed.\"\n\
+             exit 7\n",
+        );
+        let (progress, output, result) = run(&stub);
+        assert_eq!(result, Ok(()));
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[1].percent, 50.0);
+        assert!(output.iter().any(|l| l.starts_with("Success!")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stub_install_reports_error_line() {
+        let stub = stub_steamcmd(
+            "error.sh",
+            "echo \"Error! App '730' state is 0x202 after update job.\"\nexit 8\n",
+        );
+        let (_, _, result) = run(&stub);
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Error! App '730' state is 0x202 after update job."
+            ))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stub_install_reports_stderr() {
+        let stub = stub_steamcmd(
+            "stderr.sh",
+            "echo 'steamcmd.sh: libstdc++.so.6: cannot open shared object file' >&2\nexit 1\n",
+        );
+        let (_, output, result) = run(&stub);
+        let reason = "steamcmd.sh: libstdc++.so.6: cannot open shared object file";
+        assert_eq!(result, Err(String::from(reason)));
+        assert!(output.iter().any(|l| l == reason));
+    }
+}
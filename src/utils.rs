@@ -1,9 +1,37 @@
-pub fn seconds_to_minutes_str(seconds: u32) -> String {
-    let minutes: f64 = seconds as f64 / 60.0;
-    let seconds_partial: f64 = (seconds as f64 - minutes * 60.0) / 60.0;
-    return format!("{:.2}", minutes + seconds_partial);
+use directories::ProjectDirs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn project_dirs() -> Option<ProjectDirs> {
+    return ProjectDirs::from("", "", "CS2ServerPrestarter");
 }
 
 pub fn bool_to_str(val: bool) -> String {
     return String::from(if val { "1" } else { "0" });
 }
+
+// UTC date and time as `YYYY-MM-DD HH:MM:SS`
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+
+    // civil from days, http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    return format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    );
+}
@@ -0,0 +1,280 @@
+use crate::settings::ServerSettings;
+
+// Tick rates CS2 dedicated server can run with
+pub const SUPPORTED_TICKRATES: &[u32] = &[64, 128];
+
+// Characters which break either server command line or `steam://connect` link
+const FORBIDDEN_PASSWORD_CHARS: &[char] = &['"', ';', '/', '\\', '\''];
+
+const GSLT_LENGTH: usize = 32;
+
+pub const MAX_PLAYERS_LIMIT: u32 = 64;
+
+// `-port` is not configurable yet, so server always listens on default one
+pub const GAME_PORT: u16 = 27015;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub field: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub issues: Vec<Issue>,
+}
+
+impl Report {
+    pub fn for_field<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a Issue> {
+        return self.issues.iter().filter(move |i| i.field == field);
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        return self.issues.iter().filter(|i| i.severity == Severity::Error);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        return self.errors().next().is_some();
+    }
+
+    fn warning(&mut self, field: &'static str, message: String) {
+        self.issues.push(Issue {
+            field,
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    fn error(&mut self, field: &'static str, message: String) {
+        self.issues.push(Issue {
+            field,
+            severity: Severity::Error,
+            message,
+        });
+    }
+}
+
+pub fn validate(settings: &ServerSettings) -> Report {
+    let mut report = Report::default();
+
+    if settings.mp_maxrounds == 0 {
+        report.warning(
+            "mp_maxrounds",
+            String::from("Match will never end by rounds limit"),
+        );
+    } else if settings.mp_maxrounds % 2 != 0 {
+        report.warning(
+            "mp_maxrounds",
+            format!(
+                "Odd value makes halftime uneven: teams switch after {} rounds",
+                settings.mp_maxrounds / 2
+            ),
+        );
+    }
+
+    let max_players = settings.maxplayers;
+    if max_players == 0 || max_players > MAX_PLAYERS_LIMIT {
+        report.error(
+            "maxplayers",
+            format!("Must be between 1 and {MAX_PLAYERS_LIMIT}"),
+        );
+    }
+
+    if settings.sv_visiblemaxplayers > max_players as i32 {
+        report.warning(
+            "sv_visiblemaxplayers",
+            format!("Server browser will show more slots than {max_players} available"),
+        );
+    }
+
+    if settings.mp_endwarmup_player_count > max_players {
+        report.error(
+            "mp_endwarmup_player_count",
+            format!("Warmup will never end: server allows only {max_players} players"),
+        );
+    }
+
+    if !SUPPORTED_TICKRATES.contains(&settings.sv_minupdaterate) {
+        report.error(
+            "sv_minupdaterate",
+            format!("Supported values are {SUPPORTED_TICKRATES:?}"),
+        );
+    }
+
+    if settings.hostname.contains('"') {
+        report.error("hostname", String::from("Hostname must not contain quotes"));
+    }
+
+    for (field, name) in [
+        ("mp_teamname_1", &settings.mp_teamname_1),
+        ("mp_teamname_2", &settings.mp_teamname_2),
+        ("tv_name", &settings.tv_name),
+    ] {
+        if name.contains('"') {
+            report.error(field, String::from("Name must not contain quotes"));
+        }
+    }
+
+    if settings.tv_enable && settings.tv_port == GAME_PORT {
+        report.error(
+            "tv_port",
+            format!("Port {GAME_PORT} is already used by game server"),
+        );
+    }
+
+    if settings.sv_tags.contains(char::is_whitespace) {
+        report.warning(
+            "sv_tags",
+            String::from("Tags are separated by commas, spaces become part of tag"),
+        );
+    }
+
+    if settings.sv_password.is_empty() {
+        report.warning(
+            "sv_password",
+            String::from("Empty password, type \"0\" for no password"),
+        );
+    } else if settings.sv_password.chars().any(char::is_whitespace) {
+        report.error(
+            "sv_password",
+            String::from("Password must not contain spaces"),
+        );
+    } else if settings
+        .sv_password
+        .chars()
+        .any(|c| FORBIDDEN_PASSWORD_CHARS.contains(&c) || c.is_control())
+    {
+        report.error(
+            "sv_password",
+            format!("Password must not contain any of {FORBIDDEN_PASSWORD_CHARS:?}"),
+        );
+    }
+
+    let token = &settings.sv_setsteamaccount;
+    if !token.is_empty() {
+        if token.len() != GSLT_LENGTH || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+            report.error(
+                "sv_setsteamaccount",
+                format!("Login token must be {GSLT_LENGTH} hexadecimal characters"),
+            );
+        } else if settings.insecure {
+            report.warning(
+                "sv_setsteamaccount",
+                String::from("Token is ignored by Steam while server is insecure"),
+            );
+        }
+    } else if !settings.insecure {
+        report.warning(
+            "sv_setsteamaccount",
+            String::from("Secure server without login token is reachable only via LAN"),
+        );
+    }
+
+    return report;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(settings: &ServerSettings, field: &str) -> Vec<Severity> {
+        return validate(settings)
+            .for_field(field)
+            .map(|i| i.severity)
+            .collect();
+    }
+
+    #[test]
+    fn default_settings_have_no_errors() {
+        let report = validate(&ServerSettings::default());
+        assert!(!report.has_errors(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn odd_maxrounds_is_warning() {
+        let mut settings = ServerSettings {
+            mp_maxrounds: 25,
+            ..ServerSettings::default()
+        };
+        assert_eq!(issues(&settings, "mp_maxrounds"), [Severity::Warning]);
+        settings.mp_maxrounds = 24;
+        assert!(issues(&settings, "mp_maxrounds").is_empty());
+    }
+
+    #[test]
+    fn warmup_player_count_above_maxplayers_is_error() {
+        let mut settings = ServerSettings {
+            maxplayers: 10,
+            mp_endwarmup_player_count: 11,
+            ..ServerSettings::default()
+        };
+        assert_eq!(
+            issues(&settings, "mp_endwarmup_player_count"),
+            [Severity::Error]
+        );
+        settings.mp_endwarmup_player_count = 10;
+        assert!(issues(&settings, "mp_endwarmup_player_count").is_empty());
+    }
+
+    #[test]
+    fn unsupported_tickrate_is_error() {
+        let mut settings = ServerSettings::default();
+        for tickrate in [0, 60, 102, 256] {
+            settings.sv_minupdaterate = tickrate;
+            assert_eq!(issues(&settings, "sv_minupdaterate"), [Severity::Error]);
+        }
+        for &tickrate in SUPPORTED_TICKRATES {
+            settings.sv_minupdaterate = tickrate;
+            assert!(issues(&settings, "sv_minupdaterate").is_empty());
+        }
+    }
+
+    #[test]
+    fn password_characters_are_checked() {
+        let mut settings = ServerSettings::default();
+        for password in ["a\"b", "a;b", "a/b", "a\\b", "a'b", "a b", "a\tb"] {
+            settings.sv_password = String::from(password);
+            assert_eq!(
+                issues(&settings, "sv_password"),
+                [Severity::Error],
+                "{password}"
+            );
+        }
+        settings.sv_password = String::new();
+        assert_eq!(issues(&settings, "sv_password"), [Severity::Warning]);
+        settings.sv_password = String::from("s3cret-pass");
+        assert!(issues(&settings, "sv_password").is_empty());
+    }
+
+    #[test]
+    fn login_token_format_is_checked() {
+        let mut settings = ServerSettings {
+            insecure: false,
+            ..ServerSettings::default()
+        };
+        for token in [
+            "0123456789ABCDEF0123456789ABCDE",
+            "0123456789ABCDEF0123456789ABCDEF0",
+            "0123456789ABCDEF0123456789ABCDEG",
+        ] {
+            settings.sv_setsteamaccount = String::from(token);
+            assert_eq!(
+                issues(&settings, "sv_setsteamaccount"),
+                [Severity::Error],
+                "{token}"
+            );
+        }
+
+        settings.sv_setsteamaccount = String::from("0123456789ABCDEF0123456789abcdef");
+        assert!(issues(&settings, "sv_setsteamaccount").is_empty());
+        settings.insecure = true;
+        assert_eq!(issues(&settings, "sv_setsteamaccount"), [Severity::Warning]);
+    }
+}
@@ -0,0 +1,164 @@
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+// restart counter is reset once server stays up for this long
+const STABLE_UPTIME: Duration = Duration::from_secs(300);
+const MAX_BACKOFF_SHIFT: u32 = 6;
+const MAX_HISTORY: usize = 50;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct WatchdogSettings {
+    pub enabled: bool,
+    // delay before first restart, doubled on every following attempt
+    pub restart_delay_secs: u64,
+    pub max_attempts: u32,
+}
+
+impl Default for WatchdogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            restart_delay_secs: 5,
+            max_attempts: 5,
+        }
+    }
+}
+
+impl WatchdogSettings {
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let shift = attempt.saturating_sub(1).min(MAX_BACKOFF_SHIFT);
+        return Duration::from_secs(self.restart_delay_secs.saturating_mul(1 << shift));
+    }
+}
+
+pub struct RestartRecord {
+    pub crashed_at: Instant,
+    pub uptime: Duration,
+    pub exit: String,
+    // None if server was not restarted
+    pub attempt: Option<u32>,
+}
+
+#[derive(Default)]
+pub struct Watchdog {
+    attempts: u32,
+    restart_at: Option<Instant>,
+    pub history: Vec<RestartRecord>,
+}
+
+impl Watchdog {
+    pub fn attempts(&self) -> u32 {
+        return self.attempts;
+    }
+
+    // Time left until scheduled restart
+    pub fn pending(&self) -> Option<Duration> {
+        return self
+            .restart_at
+            .map(|at| at.saturating_duration_since(Instant::now()));
+    }
+
+    // Called when managed process exits without being stopped by user, returns true if restart is scheduled
+    pub fn on_exit(
+        &mut self,
+        settings: &WatchdogSettings,
+        status: &ExitStatus,
+        uptime: Duration,
+    ) -> bool {
+        if status.success() {
+            self.reset();
+            return false;
+        }
+        if uptime >= STABLE_UPTIME {
+            self.attempts = 0;
+        }
+        return self.on_failure(settings, status.to_string(), uptime);
+    }
+
+    // Called when restart could not even start the process, counts as failed attempt
+    pub fn on_spawn_failure(&mut self, settings: &WatchdogSettings, error: &str) -> bool {
+        return self.on_failure(
+            settings,
+            format!("failed to start: {error}"),
+            Duration::ZERO,
+        );
+    }
+
+    fn on_failure(&mut self, settings: &WatchdogSettings, exit: String, uptime: Duration) -> bool {
+        let restart = settings.enabled && self.attempts < settings.max_attempts;
+        if restart {
+            self.attempts += 1;
+            self.restart_at = Some(Instant::now() + settings.backoff(self.attempts));
+        }
+
+        self.history.push(RestartRecord {
+            crashed_at: Instant::now(),
+            uptime,
+            exit,
+            attempt: restart.then_some(self.attempts),
+        });
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        return restart;
+    }
+
+    // Returns true once when scheduled restart time has come
+    pub fn take_due(&mut self) -> bool {
+        let due = matches!(self.restart_at, Some(at) if at <= Instant::now());
+        if due {
+            self.restart_at = None;
+        }
+        return due;
+    }
+
+    // Drops scheduled restart and attempt counter, e.g. when user stops server
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+        self.restart_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> WatchdogSettings {
+        return WatchdogSettings {
+            enabled: true,
+            restart_delay_secs: 5,
+            max_attempts: 2,
+        };
+    }
+
+    #[test]
+    fn spawn_failures_count_against_limit() {
+        let settings = settings();
+        let mut watchdog = Watchdog::default();
+
+        assert!(watchdog.on_spawn_failure(&settings, "executable not found"));
+        assert_eq!(watchdog.attempts(), 1);
+        assert!(watchdog.pending().is_some());
+
+        assert!(watchdog.on_spawn_failure(&settings, "executable not found"));
+        assert_eq!(watchdog.attempts(), 2);
+        assert!(watchdog.pending().unwrap() > Duration::from_secs(5));
+
+        assert!(!watchdog.on_spawn_failure(&settings, "executable not found"));
+        assert_eq!(watchdog.history.len(), 3);
+        assert_eq!(watchdog.history[2].attempt, None);
+        assert_eq!(
+            watchdog.history[0].exit,
+            "failed to start: executable not found"
+        );
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        let settings = settings();
+        assert_eq!(settings.backoff(1), Duration::from_secs(5));
+        assert_eq!(settings.backoff(2), Duration::from_secs(10));
+        assert_eq!(settings.backoff(3), Duration::from_secs(20));
+    }
+}